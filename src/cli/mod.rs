@@ -1,12 +1,64 @@
 use anyhow::Result;
+use chrono::{Local, NaiveDate};
 use clap::{Parser, Subcommand};
-use std::path::PathBuf;
+use futures::StreamExt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
-use crate::core::config::Config;
-use crate::core::models::SearchOptions;
-use crate::core::patent_search::{PatentSearch, PatentSearcher};
+use crate::core::Error;
+use crate::core::batch::{self, BatchReport, DEFAULT_CONCURRENCY};
+use crate::core::cache::{self, CacheMode, PatentCache};
+use crate::core::claims::{self, ChartFormat};
+use crate::core::config::{Backend, Config};
+use crate::core::crawl::{self, CrawlOptions};
+use crate::core::dates::{DateBound, parse_date_filter};
+use crate::core::extract;
+use crate::core::family;
+use crate::core::harvest::{self, DateField, MAX_RESULTS_PER_QUERY};
+use crate::core::images;
+use crate::core::job::{JobSearcher, job_path};
+use crate::core::lint::{self, LintReport};
+use crate::core::models::{
+    Claim, Dedup, Patent, PatentStatus, PatentType, SearchOptions, SortOrder, check_page_size,
+};
+use crate::core::patent_search::{PatentSearch, create_searcher};
+use crate::core::pdf;
 use crate::mcp;
 
+/// Exit code for general failures
+pub const EXIT_FAILURE: i32 = 1;
+/// Exit code for invalid arguments (also used by clap)
+pub const EXIT_USAGE: i32 = 2;
+/// Exit code when the requested patent does not exist
+pub const EXIT_NOT_FOUND: i32 = 3;
+/// Exit code when Google blocked the request (bot detection / rate limiting)
+pub const EXIT_RATE_LIMITED: i32 = 4;
+/// Exit code for browser or network failures
+pub const EXIT_BROWSER: i32 = 5;
+
+/// Map an error to the process exit code, so scripts can tell failures apart.
+pub fn exit_code(err: &anyhow::Error) -> i32 {
+    match err.downcast_ref::<Error>() {
+        Some(Error::RateLimited(_)) => EXIT_RATE_LIMITED,
+        Some(Error::Browser(_) | Error::Request(_) | Error::WebSocket(_)) => EXIT_BROWSER,
+        Some(Error::Config(_) | Error::Url(_) | Error::InvalidDate(_)) => EXIT_USAGE,
+        _ => EXIT_FAILURE,
+    }
+}
+
+fn parse_after_date(s: &str) -> std::result::Result<NaiveDate, String> {
+    parse_date_filter(s, DateBound::Start).map_err(|e| e.to_string())
+}
+
+fn parse_before_date(s: &str) -> std::result::Result<NaiveDate, String> {
+    parse_date_filter(s, DateBound::End).map_err(|e| e.to_string())
+}
+
+fn parse_page_size(s: &str) -> std::result::Result<usize, String> {
+    let num = s.parse().map_err(|e: std::num::ParseIntError| e.to_string())?;
+    check_page_size(num).map_err(|e| e.to_string())
+}
+
 #[derive(Parser)]
 #[command(name = "google-patent-cli")]
 #[command(author, version = env!("CARGO_PKG_VERSION"), about = "A CLI for searching Google Patents", long_about = include_str!("../../README.md"))]
@@ -25,75 +77,153 @@ pub struct SearchArgs {
     #[arg(long, num_args = 1..)]
     pub assignee: Option<Vec<String>>,
 
-    /// Filter by country (JP, US, CN)
-    #[arg(long)]
-    pub country: Option<String>,
+    /// Filter by inventor
+    #[arg(long, num_args = 1..)]
+    pub inventor: Option<Vec<String>>,
+
+    /// Filter by CPC classification code (e.g., G06N20/00)
+    #[arg(long, num_args = 1..)]
+    pub cpc: Option<Vec<String>>,
+
+    /// Filter by country (e.g., JP US EP WO or JP,US,EP,WO)
+    #[arg(long, num_args = 1.., value_delimiter = ',')]
+    pub country: Option<Vec<String>>,
 
     // Priority date filters
-    /// Filter by priority date after (YYYY-MM-DD)
-    #[arg(long)]
-    pub priority_after: Option<String>,
+    /// Filter by priority date after (YYYY-MM-DD, YYYY-MM, YYYY or e.g. -5y)
+    #[arg(long, value_parser = parse_after_date, allow_hyphen_values = true)]
+    pub priority_after: Option<NaiveDate>,
 
-    /// Filter by priority date before (YYYY-MM-DD)
-    #[arg(long)]
-    pub priority_before: Option<String>,
+    /// Filter by priority date before (YYYY-MM-DD, YYYY-MM, YYYY or e.g. -5y)
+    #[arg(long, value_parser = parse_before_date, allow_hyphen_values = true)]
+    pub priority_before: Option<NaiveDate>,
 
     // Publication date filters
-    /// Filter by publication date after (YYYY-MM-DD)
-    #[arg(long)]
-    pub publication_after: Option<String>,
+    /// Filter by publication date after (YYYY-MM-DD, YYYY-MM, YYYY or e.g. -5y)
+    #[arg(long, value_parser = parse_after_date, allow_hyphen_values = true)]
+    pub publication_after: Option<NaiveDate>,
 
-    /// Filter by publication date before (YYYY-MM-DD)
-    #[arg(long)]
-    pub publication_before: Option<String>,
+    /// Filter by publication date before (YYYY-MM-DD, YYYY-MM, YYYY or e.g. -5y)
+    #[arg(long, value_parser = parse_before_date, allow_hyphen_values = true)]
+    pub publication_before: Option<NaiveDate>,
 
     // Filing date filters
-    /// Filter by filing date after (YYYY-MM-DD)
-    #[arg(long)]
-    pub filing_after: Option<String>,
+    /// Filter by filing date after (YYYY-MM-DD, YYYY-MM, YYYY or e.g. -5y)
+    #[arg(long, value_parser = parse_after_date, allow_hyphen_values = true)]
+    pub filing_after: Option<NaiveDate>,
 
-    /// Filter by filing date before (YYYY-MM-DD)
-    #[arg(long)]
-    pub filing_before: Option<String>,
+    /// Filter by filing date before (YYYY-MM-DD, YYYY-MM, YYYY or e.g. -5y)
+    #[arg(long, value_parser = parse_before_date, allow_hyphen_values = true)]
+    pub filing_before: Option<NaiveDate>,
+
+    /// Filter by publication status
+    #[arg(long, value_enum)]
+    pub status: Option<PatentStatus>,
+
+    /// Filter by patent type
+    #[arg(long = "type", value_enum)]
+    pub patent_type: Option<PatentType>,
+
+    /// Only patents involved in litigation
+    #[arg(long, default_value_t = false)]
+    pub litigation: bool,
+
+    /// Sort results by date instead of relevance
+    #[arg(long, value_enum)]
+    pub sort: Option<SortOrder>,
+
+    /// Deduplicate results by family or by publication language
+    #[arg(long, value_enum)]
+    pub dedup: Option<Dedup>,
 
     /// Limit the number of results
     #[arg(short, long)]
     pub limit: Option<usize>,
 
-    /// Run with visible browser window (default is headless)
+    /// Fetch all results, splitting the date range into windows Google Patents can page through
     #[arg(long, default_value_t = false)]
-    pub head: bool,
+    pub harvest: bool,
+
+    /// Date filter used to split a harvest into windows
+    #[arg(long, value_enum, default_value_t = DateField::Filing, requires = "harvest")]
+    pub harvest_by: DateField,
+
+    /// Checkpoint progress to a job (name or .jsonl path), resuming it if it already exists
+    #[arg(long, value_name = "JOB")]
+    pub resume: Option<String>,
+
+    /// Results per page (10, 20, 50 or 100; defaults to fit --limit)
+    #[arg(long, value_parser = parse_page_size)]
+    pub num: Option<usize>,
 
     /// Output as JSON (default is JSON, but flag kept for clarity)
     #[arg(long, default_value_t = true)]
     pub json: bool,
 
-    /// Debug: Connect to existing browser WS URL
-    #[arg(long)]
-    pub debug_ws_url: Option<String>,
-
-    /// Enable debug output (shows Chrome logs)
-    #[arg(long, default_value_t = false)]
-    pub debug: bool,
-
-    /// Enable verbose output (shows detailed progress)
-    #[arg(long, default_value_t = false)]
-    pub verbose: bool,
+    /// Parse a saved search results page instead of searching online
+    #[arg(long, value_name = "FILE")]
+    pub from_html: Option<PathBuf>,
 
-    /// Language/locale for patent pages (e.g., ja, en, zh)
-    #[arg(long)]
-    pub language: Option<String>,
+    #[command(flatten)]
+    pub browser: BrowserArgs,
 }
 
 #[derive(clap::Args, Debug)]
 pub struct FetchArgs {
     /// Patent ID (e.g., US1234567)
-    pub patent_id: String,
+    #[arg(required_unless_present = "batch")]
+    pub patent_id: Option<String>,
+
+    /// Fetch all patent IDs listed in a file, one per line ("-" reads from stdin)
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["patent_id", "from_html"])]
+    pub batch: Option<String>,
+
+    /// Maximum number of patents fetched at the same time in batch mode
+    #[arg(long, default_value_t = DEFAULT_CONCURRENCY)]
+    pub concurrency: usize,
+
+    /// Write one JSON file per patent into this directory instead of JSONL to stdout
+    #[arg(long, value_name = "DIR", conflicts_with = "patent_id")]
+    pub output_dir: Option<PathBuf>,
 
+    /// Checkpoint batch progress to a job (name or .jsonl path), resuming it if it already exists
+    #[arg(long, value_name = "JOB", conflicts_with = "patent_id")]
+    pub resume: Option<String>,
+
+    /// Download every figure into DIR/<patent ID>/ with a manifest.json
+    #[arg(long, value_name = "DIR", conflicts_with_all = ["raw", "from_html"])]
+    pub download_images: Option<PathBuf>,
+
+    /// Download the publication PDF as DIR/<patent ID>.pdf
+    #[arg(long, value_name = "DIR", conflicts_with_all = ["raw", "from_html"])]
+    pub pdf: Option<PathBuf>,
+
+    /// Parse a saved patent page instead of fetching it online
+    #[arg(long, value_name = "FILE")]
+    pub from_html: Option<PathBuf>,
+
+    /// Print the full rendered page HTML instead of JSON (for debugging extraction)
+    #[arg(long, default_value_t = false, conflicts_with_all = ["batch", "from_html", "offline"])]
+    pub raw: bool,
+
+    #[command(flatten)]
+    pub browser: BrowserArgs,
+
+    #[command(flatten)]
+    pub cache: CacheArgs,
+}
+
+/// Browser options of the subcommands that go online
+#[derive(clap::Args, Debug)]
+pub struct BrowserArgs {
     /// Run with visible browser window (default is headless)
     #[arg(long, default_value_t = false)]
     pub head: bool,
 
+    /// Attach to a running browser via its DevTools URL instead of launching one
+    #[arg(long, value_name = "URL")]
+    pub debug_ws_url: Option<String>,
+
     /// Enable debug output (shows Chrome logs)
     #[arg(long, default_value_t = false)]
     pub debug: bool,
@@ -105,6 +235,159 @@ pub struct FetchArgs {
     /// Language/locale for patent pages (e.g., ja, en, zh)
     #[arg(long)]
     pub language: Option<String>,
+
+    /// Backend used to retrieve pages (defaults to the configured backend)
+    #[arg(long, value_enum)]
+    pub backend: Option<Backend>,
+}
+
+impl BrowserArgs {
+    /// Load the config, with `--debug-ws-url` taking precedence over the configured one.
+    fn config(&self) -> Result<Config> {
+        let mut config = Config::load()?;
+        config.debug_ws_url = self.debug_ws_url.clone().or(config.debug_ws_url);
+        Ok(config)
+    }
+
+    /// Create the searcher these options select.
+    async fn build_searcher(&self, config: &Config) -> Result<Arc<dyn PatentSearch>> {
+        let backend = self.backend.unwrap_or(config.backend);
+        Ok(create_searcher(config, backend, !self.head, self.debug, self.verbose).await?)
+    }
+}
+
+/// Patent cache options of the subcommands that fetch patents
+#[derive(clap::Args, Debug)]
+pub struct CacheArgs {
+    /// Don't read from or write to the patent cache
+    #[arg(long, default_value_t = false, conflicts_with = "offline")]
+    pub no_cache: bool,
+
+    /// Fetch again even if the patent is cached, then update the cache
+    #[arg(long, default_value_t = false, conflicts_with = "offline")]
+    pub refresh: bool,
+
+    /// Serve only from the patent cache, without going online
+    #[arg(long, default_value_t = false)]
+    pub offline: bool,
+}
+
+impl CacheArgs {
+    /// Open the patent cache (unless `--no-cache`) and the cache mode these options select.
+    fn open(&self, config: &Config) -> Result<(Option<PatentCache>, CacheMode)> {
+        let cache = if self.no_cache { None } else { Some(PatentCache::from_config(config)?) };
+        Ok((cache, CacheMode::from_flags(self.refresh, self.offline)))
+    }
+}
+
+/// Fetch one patent through the cache, exiting with [`EXIT_NOT_FOUND`] if there is none.
+async fn fetch_one_patent(
+    browser: &BrowserArgs,
+    cache_args: &CacheArgs,
+    patent_id: &str,
+) -> Result<Patent> {
+    let config = browser.config()?;
+    let (cache, mode) = cache_args.open(&config)?;
+    let searcher = browser.build_searcher(&config).await?;
+    let options = SearchOptions {
+        patent_number: Some(patent_id.to_string()),
+        language: browser.language.clone(),
+        ..Default::default()
+    };
+    let patent = cache::fetch_patent(searcher.as_ref(), cache.as_ref(), &options, mode).await;
+    searcher.shutdown().await;
+    let Some(patent) = patent? else {
+        eprintln!("No patent found with ID: {}", patent_id);
+        std::process::exit(EXIT_NOT_FOUND);
+    };
+    Ok(patent)
+}
+
+#[derive(clap::Args, Debug)]
+pub struct FamilyArgs {
+    /// Patent ID the family is expanded from (e.g., US9152718B2)
+    pub patent_id: String,
+
+    /// Maximum number of family members fetched at the same time
+    #[arg(long, default_value_t = DEFAULT_CONCURRENCY)]
+    pub concurrency: usize,
+
+    #[command(flatten)]
+    pub browser: BrowserArgs,
+
+    #[command(flatten)]
+    pub cache: CacheArgs,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct ClaimsArgs {
+    /// Patent ID (e.g., US9152718B2)
+    pub patent_id: String,
+
+    /// Print a claim chart of the independent claims instead of the parsed claims
+    #[arg(long, default_value_t = false)]
+    pub chart: bool,
+
+    /// Table format of the claim chart
+    #[arg(long, value_enum, default_value_t = ChartFormat::Markdown, requires = "chart")]
+    pub format: ChartFormat,
+
+    /// Parse a saved patent page instead of fetching it
+    #[arg(long, value_name = "FILE")]
+    pub from_html: Option<PathBuf>,
+
+    #[command(flatten)]
+    pub browser: BrowserArgs,
+
+    #[command(flatten)]
+    pub cache: CacheArgs,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct LintClaimsArgs {
+    /// Patent ID whose claims are checked (e.g., US9152718B2)
+    #[arg(required_unless_present = "file")]
+    pub patent_id: Option<String>,
+
+    /// Check claims from a JSON file instead: a patent, a list of claims or of claim texts
+    /// ("-" reads from stdin)
+    #[arg(long, value_name = "FILE", conflicts_with = "patent_id")]
+    pub file: Option<String>,
+
+    #[command(flatten)]
+    pub browser: BrowserArgs,
+
+    #[command(flatten)]
+    pub cache: CacheArgs,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct CrawlArgs {
+    /// Patent IDs the crawl starts from
+    #[arg(required = true, num_args = 1..)]
+    pub seeds: Vec<String>,
+
+    /// Hops followed through backward citations (what the seeds cite)
+    #[arg(long, default_value_t = 2)]
+    pub backward_depth: usize,
+
+    /// Hops followed through forward citations (what cites the seeds)
+    #[arg(long, default_value_t = 1)]
+    pub forward_depth: usize,
+
+    /// Most patents fetched, seeds included
+    #[arg(long, default_value_t = 200)]
+    pub max_nodes: usize,
+
+    /// Maximum number of patents fetched at the same time
+    #[arg(long, default_value_t = DEFAULT_CONCURRENCY)]
+    pub concurrency: usize,
+
+    #[command(flatten)]
+    pub browser: BrowserArgs,
+
+    #[command(flatten)]
+    pub cache: CacheArgs,
 }
 
 #[derive(Subcommand)]
@@ -120,6 +403,26 @@ pub enum Commands {
         #[command(flatten)]
         args: FetchArgs,
     },
+    /// Fetch a patent's family and show where it was granted, is pending or was abandoned
+    Family {
+        #[command(flatten)]
+        args: FamilyArgs,
+    },
+    /// Show a patent's parsed claims or a claim chart
+    Claims {
+        #[command(flatten)]
+        args: ClaimsArgs,
+    },
+    /// Check claims for antecedent basis, bad references and means-plus-function wording
+    LintClaims {
+        #[command(flatten)]
+        args: LintClaimsArgs,
+    },
+    /// Walk the citation graph around seed patents
+    Crawl {
+        #[command(flatten)]
+        args: CrawlArgs,
+    },
     /// Configure the CLI
     Config {
         /// Set the path to the browser executable
@@ -130,6 +433,21 @@ pub enum Commands {
     Mcp,
 }
 
+/// Wrap the searcher in a checkpointing job when `--resume` is given.
+fn with_job(searcher: Arc<dyn PatentSearch>, job: Option<&str>) -> Result<Arc<dyn PatentSearch>> {
+    let Some(job) = job else { return Ok(searcher) };
+    Ok(open_job(searcher, job)?)
+}
+
+fn open_job(searcher: Arc<dyn PatentSearch>, job: &str) -> Result<Arc<JobSearcher>> {
+    let path = job_path(job)?;
+    let job = JobSearcher::open(searcher, &path)?;
+    if job.completed() > 0 {
+        eprintln!("Resuming job {} ({} completed steps)", path.display(), job.completed());
+    }
+    Ok(Arc::new(job))
+}
+
 pub async fn run() -> Result<()> {
     let cli = Cli::parse();
     run_app(cli).await
@@ -152,24 +470,31 @@ pub async fn run_app(cli: Cli) -> Result<()> {
             }
         }
         Commands::Search { args } => {
-            if args.query.is_none() && args.assignee.is_none() {
-                anyhow::bail!("At least one of --query or --assignee must be provided.");
+            if let Some(path) = &args.from_html {
+                let html = std::fs::read_to_string(path)?;
+                let mut results = extract::parse_search_results_html(&html);
+                if let Some(limit) = args.limit {
+                    results.patents.truncate(limit);
+                }
+                println!("{}", serde_json::to_string_pretty(&results)?);
+                return Ok(());
             }
 
-            let config = Config::load()?;
-            let (browser_path, chrome_args) = config.resolve();
-            let searcher = PatentSearcher::new(
-                browser_path,
-                !args.head,
-                args.debug,
-                args.verbose,
-                chrome_args,
-            )
-            .await?;
+            if args.query.is_none()
+                && args.assignee.is_none()
+                && args.inventor.is_none()
+                && args.cpc.is_none()
+            {
+                anyhow::bail!(
+                    "At least one of --query, --assignee, --inventor or --cpc must be provided."
+                );
+            }
 
-            let options = SearchOptions {
+            let mut options = SearchOptions {
                 query: args.query,
                 assignee: args.assignee,
+                inventor: args.inventor,
+                cpc: args.cpc,
                 country: args.country,
                 patent_number: None,
                 priority_after: args.priority_after,
@@ -178,51 +503,320 @@ pub async fn run_app(cli: Cli) -> Result<()> {
                 publication_before: args.publication_before,
                 filing_after: args.filing_after,
                 filing_before: args.filing_before,
+                status: args.status,
+                patent_type: args.patent_type,
+                litigation: args.litigation,
+                sort: args.sort,
+                dedup: args.dedup,
                 limit: args.limit,
-                language: args.language,
+                num: args.num,
+                start_page: 0,
+                language: args.browser.language.clone(),
             };
+            options.validate_dates()?;
 
-            let results = searcher.search(&options).await?;
+            let config = args.browser.config()?;
+            let searcher = args.browser.build_searcher(&config).await?;
+            let results = async {
+                let searcher: Arc<dyn PatentSearch> = match args.resume.as_deref() {
+                    Some(name) => {
+                        let job = open_job(searcher.clone(), name)?;
+                        if args.harvest {
+                            let today = Local::now().date_naive();
+                            harvest::pin_range(&mut options, args.harvest_by, &job, today)?;
+                        }
+                        job
+                    }
+                    None => searcher.clone(),
+                };
+                let results = if args.harvest {
+                    harvest::harvest(
+                        searcher.as_ref(),
+                        &options,
+                        args.harvest_by,
+                        MAX_RESULTS_PER_QUERY,
+                        args.browser.verbose,
+                    )
+                    .await?
+                } else {
+                    searcher.search(&options).await?
+                };
+                anyhow::Ok(results)
+            }
+            .await;
+            searcher.shutdown().await;
+            let results = results?;
             let json = serde_json::to_string_pretty(&results)?;
             println!("{}", json);
         }
         Commands::Fetch { args } => {
-            let config = Config::load()?;
-            let (browser_path, chrome_args) = config.resolve();
-            let searcher = PatentSearcher::new(
-                browser_path,
-                !args.head,
-                args.debug,
-                args.verbose,
-                chrome_args,
-            )
-            .await?;
+            if let Some(source) = &args.batch {
+                return run_fetch_batch(&args, source).await;
+            }
+            let patent_id = args
+                .patent_id
+                .clone()
+                .ok_or_else(|| anyhow::anyhow!("A patent ID or --batch must be provided."))?;
+
+            if let Some(path) = &args.from_html {
+                let html = std::fs::read_to_string(path)?;
+                let options = SearchOptions {
+                    patent_number: Some(patent_id.clone()),
+                    language: args.browser.language,
+                    ..Default::default()
+                };
+                let patent = extract::parse_patent_html(&html, &patent_id, &options.to_url()?);
+                println!("{}", serde_json::to_string_pretty(&patent)?);
+                return Ok(());
+            }
+
+            let config = args.browser.config()?;
+            let (cache, mode) = args.cache.open(&config)?;
+            let searcher = args.browser.build_searcher(&config).await?;
 
             let options = SearchOptions {
-                query: None,
-                assignee: None,
-                country: None,
-                patent_number: Some(args.patent_id.clone()),
-                priority_after: None,
-                priority_before: None,
-                publication_after: None,
-                publication_before: None,
-                filing_after: None,
-                filing_before: None,
-                limit: None,
-                language: args.language,
+                patent_number: Some(patent_id.clone()),
+                language: args.browser.language,
+                ..Default::default()
             };
-            let mut results = searcher.search(&options).await?;
-            if let Some(patent) = results.patents.pop() {
+
+            if args.raw {
+                let html = searcher.fetch_raw_html(&options).await;
+                searcher.shutdown().await;
+                println!("{}", html?);
+                return Ok(());
+            }
+
+            let patent =
+                cache::fetch_patent(searcher.as_ref(), cache.as_ref(), &options, mode).await;
+            searcher.shutdown().await;
+            if let Some(patent) = patent? {
+                if let Some(dir) = &args.download_images {
+                    save_images(&patent, dir, config.image_host()).await?;
+                }
+                if let Some(dir) = &args.pdf {
+                    let download = pdf::download_pdf(&patent, dir, config.image_host()).await?;
+                    if !download.skipped {
+                        eprintln!("Saved {}", download.file.display());
+                    }
+                }
                 let json = serde_json::to_string_pretty(&patent)?;
                 println!("{}", json);
             } else {
+                eprintln!("No patent found with ID: {}", patent_id);
+                std::process::exit(EXIT_NOT_FOUND);
+            }
+        }
+        Commands::Family { args } => {
+            let config = args.browser.config()?;
+            let (cache, mode) = args.cache.open(&config)?;
+            let searcher = args.browser.build_searcher(&config).await?;
+            let family = family::fetch_family(
+                searcher.as_ref(),
+                cache.as_ref(),
+                &args.patent_id,
+                args.browser.language,
+                mode,
+                args.concurrency,
+            )
+            .await;
+            searcher.shutdown().await;
+            let Some(family) = family? else {
                 eprintln!("No patent found with ID: {}", args.patent_id);
-                std::process::exit(1);
+                std::process::exit(EXIT_NOT_FOUND);
+            };
+            for member in &family.members {
+                if let (Some(publication), Some(error)) =
+                    (&member.publication_number, &member.error)
+                {
+                    eprintln!("Failed to fetch {}: {}", publication, error);
+                }
+            }
+            println!("{}", serde_json::to_string_pretty(&family)?);
+            if family.rate_limited {
+                std::process::exit(EXIT_RATE_LIMITED);
+            }
+        }
+        Commands::Claims { args } => {
+            let patent = if let Some(path) = &args.from_html {
+                let html = std::fs::read_to_string(path)?;
+                let options = SearchOptions {
+                    patent_number: Some(args.patent_id.clone()),
+                    language: args.browser.language.clone(),
+                    ..Default::default()
+                };
+                extract::parse_patent_html(&html, &args.patent_id, &options.to_url()?)
+            } else {
+                fetch_one_patent(&args.browser, &args.cache, &args.patent_id).await?
+            };
+
+            let claims = patent.claims.unwrap_or_default();
+            if claims.is_empty() {
+                eprintln!("No claims found for {}", patent.id);
+            }
+            print!("{}", render_claims(&claims, &args)?);
+        }
+        Commands::LintClaims { args } => {
+            let report = if let Some(source) = &args.file {
+                lint_file(source)?
+            } else {
+                let patent_id = args
+                    .patent_id
+                    .clone()
+                    .ok_or_else(|| anyhow::anyhow!("A patent ID or --file must be provided."))?;
+                let patent = fetch_one_patent(&args.browser, &args.cache, &patent_id).await?;
+                lint::lint_claims(Some(patent.id), patent.claims.as_deref().unwrap_or_default())
+            };
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        }
+        Commands::Crawl { args } => {
+            let config = args.browser.config()?;
+            let (cache, mode) = args.cache.open(&config)?;
+            let searcher = args.browser.build_searcher(&config).await?;
+            let options = CrawlOptions {
+                backward_depth: args.backward_depth,
+                forward_depth: args.forward_depth,
+                max_nodes: args.max_nodes,
+                concurrency: args.concurrency,
+                language: args.browser.language,
+                mode,
+            };
+            let seeds = batch::parse_id_list(&args.seeds.join("\n"));
+            let verbose = args.browser.verbose;
+            let result =
+                crawl::crawl(searcher.as_ref(), cache.as_ref(), &seeds, &options, verbose).await;
+            searcher.shutdown().await;
+            let result = result?;
+            if result.budget_exhausted {
+                eprintln!("Warning: stopped after {} patents (--max-nodes)", result.fetched);
+            }
+            println!("{}", serde_json::to_string_pretty(&result)?);
+            if result.rate_limited {
+                eprintln!("Google blocked the crawl after {} patents", result.fetched);
+                std::process::exit(EXIT_RATE_LIMITED);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Lint the claims in a JSON file ("-" reads from stdin).
+fn lint_file(source: &str) -> Result<LintReport> {
+    let text = if source == "-" {
+        std::io::read_to_string(std::io::stdin())?
+    } else {
+        std::fs::read_to_string(source)?
+    };
+    let value: serde_json::Value = serde_json::from_str(&text)?;
+    let claims = lint::claims_from_json(&value)?;
+    let patent_id = value.get("id").and_then(|id| id.as_str()).map(String::from);
+    Ok(lint::lint_claims(patent_id, &claims))
+}
+
+/// Render claims as JSON, or as a claim chart with `--chart`.
+fn render_claims(claims: &[Claim], args: &ClaimsArgs) -> Result<String> {
+    if args.chart {
+        return Ok(claims::render_chart(&claims::chart_claims(claims), args.format));
+    }
+    Ok(format!("{}\n", serde_json::to_string_pretty(claims)?))
+}
+
+/// Download the figures of `patent` and report the outcome on stderr.
+async fn save_images(patent: &Patent, dir: &Path, host: &str) -> Result<()> {
+    let manifest = images::download_images(patent, dir, host).await?;
+    eprintln!(
+        "Downloaded {} figures of {} ({} already present, {} failed)",
+        manifest.downloaded,
+        patent.id,
+        manifest.skipped,
+        manifest.failed.len()
+    );
+    for failure in &manifest.failed {
+        eprintln!("  {}: {}", failure.url, failure.error);
+    }
+    Ok(())
+}
+
+/// Fetch every patent listed in `source` and write JSONL to stdout or one file per patent.
+async fn run_fetch_batch(args: &FetchArgs, source: &str) -> Result<()> {
+    let text = if source == "-" {
+        std::io::read_to_string(std::io::stdin())?
+    } else {
+        std::fs::read_to_string(source)?
+    };
+    let patent_ids = batch::parse_id_list(&text);
+    if patent_ids.is_empty() {
+        anyhow::bail!("No patent IDs found in {}", source);
+    }
+
+    if let Some(dir) = &args.output_dir {
+        std::fs::create_dir_all(dir)?;
+    }
+
+    let config = args.browser.config()?;
+    let (cache, mode) = args.cache.open(&config)?;
+    let searcher = args.browser.build_searcher(&config).await?;
+
+    let mut report = BatchReport { requested: patent_ids.len(), ..Default::default() };
+    let written: Result<()> = async {
+        let job = with_job(searcher.clone(), args.resume.as_deref())?;
+        let mut items = std::pin::pin!(batch::fetch_batch(
+            job.as_ref(),
+            cache.as_ref(),
+            patent_ids,
+            args.browser.language.clone(),
+            mode,
+            args.concurrency,
+        ));
+        while let Some(item) = items.next().await {
+            report.record(&item);
+            match &item.result {
+                Ok(patent) => {
+                    if let Some(dir) = &args.download_images {
+                        save_images(patent, dir, config.image_host()).await?;
+                    }
+                    if let Some(dir) = &args.pdf {
+                        // A missing PDF should not stop the rest of the batch
+                        if let Err(e) = pdf::download_pdf(patent, dir, config.image_host()).await {
+                            eprintln!("Failed to download PDF of {}: {}", patent.id, e);
+                        }
+                    }
+                    if let Some(dir) = &args.output_dir {
+                        let path = dir.join(format!("{}.json", batch::file_stem(&patent.id)));
+                        std::fs::write(path, serde_json::to_string_pretty(patent)?)?;
+                    } else {
+                        println!("{}", serde_json::to_string(patent)?);
+                    }
+                    if args.browser.verbose {
+                        eprintln!("Fetched {}", item.patent_id);
+                    }
+                }
+                Err(e) => eprintln!("Failed to fetch {}: {}", item.patent_id, e),
             }
         }
+        Ok(())
     }
+    .await;
+    searcher.shutdown().await;
+    written?;
 
+    eprintln!(
+        "Fetched {}/{} patents ({} failed)",
+        report.succeeded,
+        report.requested,
+        report.failed.len()
+    );
+    for failure in &report.failed {
+        eprintln!("  {}: {}", failure.patent_id, failure.error);
+    }
+
+    if report.rate_limited {
+        std::process::exit(EXIT_RATE_LIMITED);
+    }
+    if !report.failed.is_empty() {
+        std::process::exit(EXIT_FAILURE);
+    }
     Ok(())
 }
 
@@ -230,6 +824,7 @@ pub async fn run_app(cli: Cli) -> Result<()> {
 #[allow(clippy::unwrap_used, clippy::expect_used)]
 mod tests {
     use super::*;
+    use crate::core::lint::IssueKind;
 
     #[test]
     fn test_cli_parsing() {
@@ -248,6 +843,273 @@ mod tests {
         assert!(cli.is_ok());
     }
 
+    #[test]
+    fn test_backend_args() {
+        let cli = Cli::try_parse_from(["google-patent-cli", "fetch", "US123", "--backend", "http"]);
+        assert!(cli.is_ok());
+
+        let cli = Cli::try_parse_from(["google-patent-cli", "fetch", "US123", "--backend", "foo"]);
+        assert!(cli.is_err());
+    }
+
+    #[test]
+    fn test_fetch_cache_args() {
+        let cli = Cli::try_parse_from(["google-patent-cli", "fetch", "US123", "--refresh"]);
+        assert!(cli.is_ok());
+
+        let cli =
+            Cli::try_parse_from(["google-patent-cli", "fetch", "US123", "--offline", "--refresh"]);
+        assert!(cli.is_err());
+
+        // Search results are never cached
+        let cli = Cli::try_parse_from(["google-patent-cli", "search", "-q", "x", "--no-cache"]);
+        assert!(cli.is_err());
+    }
+
+    #[test]
+    fn test_fetch_batch_args() {
+        let cli = Cli::try_parse_from(["google-patent-cli", "fetch", "--batch", "ids.txt"]);
+        assert!(cli.is_ok());
+
+        // Either a patent ID or --batch is required, but not both
+        let cli = Cli::try_parse_from(["google-patent-cli", "fetch"]);
+        assert!(cli.is_err());
+        let cli = Cli::try_parse_from(["google-patent-cli", "fetch", "US123", "--batch", "-"]);
+        assert!(cli.is_err());
+    }
+
+    #[test]
+    fn test_fetch_raw_args() {
+        let cli = Cli::try_parse_from(["google-patent-cli", "fetch", "US123", "--raw"]);
+        assert!(cli.is_ok());
+
+        let cli =
+            Cli::try_parse_from(["google-patent-cli", "fetch", "US123", "--raw", "--offline"]);
+        assert!(cli.is_err());
+    }
+
+    #[test]
+    fn test_debug_ws_url_args() {
+        let cli = Cli::try_parse_from([
+            "google-patent-cli",
+            "fetch",
+            "US123",
+            "--debug-ws-url",
+            "ws://127.0.0.1:9222/devtools/browser/abc",
+        ]);
+        assert!(cli.is_ok());
+    }
+
+    #[test]
+    fn test_search_filter_args() {
+        let cli = Cli::try_parse_from([
+            "google-patent-cli",
+            "search",
+            "--inventor",
+            "Jane Doe",
+            "--status",
+            "grant",
+            "--type",
+            "design",
+            "--sort",
+            "new",
+            "--dedup",
+            "language",
+            "--litigation",
+        ]);
+        assert!(cli.is_ok());
+
+        let cli = Cli::try_parse_from(["google-patent-cli", "search", "-q", "x", "--sort", "foo"]);
+        assert!(cli.is_err());
+
+        // Only the page sizes Google Patents serves are accepted
+        let cli = Cli::try_parse_from(["google-patent-cli", "search", "-q", "x", "--num", "50"]);
+        assert!(cli.is_ok());
+        let cli = Cli::try_parse_from(["google-patent-cli", "search", "-q", "x", "--num", "7"]);
+        assert!(cli.is_err());
+    }
+
+    #[test]
+    fn test_search_country_args() {
+        // Countries may be given space- or comma-separated
+        let cli = Cli::try_parse_from([
+            "google-patent-cli",
+            "search",
+            "-q",
+            "x",
+            "--country",
+            "JP,US",
+            "EP",
+        ])
+        .unwrap();
+        let Commands::Search { args } = cli.command else { panic!("expected search") };
+        assert_eq!(args.country.unwrap(), ["JP", "US", "EP"]);
+    }
+
+    #[test]
+    fn test_search_date_args() {
+        // Dates are validated at parse time; shorthands are accepted
+        let cli = Cli::try_parse_from([
+            "google-patent-cli",
+            "search",
+            "-q",
+            "x",
+            "--filing-after",
+            "2020",
+            "--filing-before",
+            "2020-06",
+        ])
+        .unwrap();
+        let Commands::Search { args } = cli.command else { panic!("expected search") };
+        assert_eq!(args.filing_after, NaiveDate::from_ymd_opt(2020, 1, 1));
+        assert_eq!(args.filing_before, NaiveDate::from_ymd_opt(2020, 6, 30));
+
+        let cli = Cli::try_parse_from([
+            "google-patent-cli",
+            "search",
+            "-q",
+            "x",
+            "--priority-after",
+            "yesterday",
+        ]);
+        assert!(cli.is_err());
+
+        // Relative dates start with a hyphen but are values, not flags
+        let cli = Cli::try_parse_from([
+            "google-patent-cli",
+            "search",
+            "--query",
+            "blockchain",
+            "--publication-after",
+            "-5y",
+            "--filing-before",
+            "-6m",
+        ])
+        .unwrap();
+        let Commands::Search { args } = cli.command else { panic!("expected search") };
+        assert!(args.publication_after.is_some());
+        assert!(args.filing_before.is_some());
+    }
+
+    #[test]
+    fn test_harvest_args() {
+        let cli = Cli::try_parse_from([
+            "google-patent-cli",
+            "search",
+            "-q",
+            "x",
+            "--harvest",
+            "--harvest-by",
+            "priority",
+        ]);
+        assert!(cli.is_ok());
+
+        // --harvest-by only makes sense with --harvest
+        let cli = Cli::try_parse_from([
+            "google-patent-cli",
+            "search",
+            "-q",
+            "x",
+            "--harvest-by",
+            "priority",
+        ]);
+        assert!(cli.is_err());
+    }
+
+    #[test]
+    fn test_resume_args() {
+        let cli =
+            Cli::try_parse_from(["google-patent-cli", "fetch", "--batch", "-", "--resume", "j"]);
+        assert!(cli.is_ok());
+
+        let cli = Cli::try_parse_from(["google-patent-cli", "fetch", "US123", "--resume", "j"]);
+        assert!(cli.is_err());
+    }
+
+    #[test]
+    fn test_family_args() {
+        let cli = Cli::try_parse_from(["google-patent-cli", "family", "US9152718B2", "--offline"]);
+        assert!(cli.is_ok());
+
+        let cli = Cli::try_parse_from(["google-patent-cli", "family"]);
+        assert!(cli.is_err());
+    }
+
+    #[test]
+    fn test_crawl_args() {
+        let cli = Cli::try_parse_from([
+            "google-patent-cli",
+            "crawl",
+            "US1B2",
+            "US2B2",
+            "--backward-depth",
+            "3",
+            "--max-nodes",
+            "50",
+        ])
+        .unwrap();
+        let Commands::Crawl { args } = cli.command else { panic!("expected crawl") };
+        assert_eq!(args.seeds, ["US1B2", "US2B2"]);
+        assert_eq!((args.backward_depth, args.forward_depth), (3, 1));
+
+        let cli = Cli::try_parse_from(["google-patent-cli", "crawl"]);
+        assert!(cli.is_err());
+    }
+
+    #[test]
+    fn test_fetch_download_args() {
+        let cli =
+            Cli::try_parse_from(["google-patent-cli", "fetch", "US123", "--download-images", "f"]);
+        assert!(cli.is_ok());
+
+        let cli = Cli::try_parse_from([
+            "google-patent-cli",
+            "fetch",
+            "US123",
+            "--download-images",
+            "f",
+            "--raw",
+        ]);
+        assert!(cli.is_err());
+
+        let cli = Cli::try_parse_from(["google-patent-cli", "fetch", "US123", "--pdf", "pdfs"]);
+        assert!(cli.is_ok());
+    }
+
+    #[test]
+    fn test_claims_args() {
+        let cli = Cli::try_parse_from(["google-patent-cli", "claims", "US123", "--chart"]);
+        assert!(cli.is_ok());
+
+        // --format only applies to charts
+        let cli = Cli::try_parse_from(["google-patent-cli", "claims", "US123", "--format", "csv"]);
+        assert!(cli.is_err());
+    }
+
+    #[test]
+    fn test_lint_claims_args() {
+        let cli = Cli::try_parse_from(["google-patent-cli", "lint-claims", "US123"]);
+        assert!(cli.is_ok());
+        let cli = Cli::try_parse_from(["google-patent-cli", "lint-claims", "--file", "-"]);
+        assert!(cli.is_ok());
+
+        // Either a patent ID or --file is required, but not both
+        let cli = Cli::try_parse_from(["google-patent-cli", "lint-claims"]);
+        assert!(cli.is_err());
+        let cli =
+            Cli::try_parse_from(["google-patent-cli", "lint-claims", "US123", "--file", "c.json"]);
+        assert!(cli.is_err());
+    }
+
+    #[test]
+    fn test_exit_code() {
+        let err: anyhow::Error = Error::RateLimited("Sorry...".to_string()).into();
+        assert_eq!(exit_code(&err), EXIT_RATE_LIMITED);
+        let err: anyhow::Error = Error::Browser("crashed".to_string()).into();
+        assert_eq!(exit_code(&err), EXIT_BROWSER);
+        assert_eq!(exit_code(&anyhow::anyhow!("other")), EXIT_FAILURE);
+    }
+
     #[tokio::test]
     async fn test_run_app_config_list() {
         // This will print to stdout, but we can check if it returns Ok
@@ -265,8 +1127,97 @@ mod tests {
         assert!(res.is_err());
         assert_eq!(
             res.unwrap_err().to_string(),
-            "At least one of --query or --assignee must be provided."
+            "At least one of --query, --assignee, --inventor or --cpc must be provided."
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_app_fetch_from_html() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let html_path = temp_dir.path().join("patent.html");
+        std::fs::write(&html_path, include_str!("../core/fixtures/patent_US9152718B2.html"))
+            .unwrap();
+
+        let cli = Cli::try_parse_from([
+            "google-patent-cli",
+            "fetch",
+            "US9152718B2",
+            "--from-html",
+            html_path.to_str().unwrap(),
+        ])
+        .unwrap();
+        assert!(run_app(cli).await.is_ok());
+
+        // Search mode doesn't require --query when parsing a saved page
+        let html_path = temp_dir.path().join("search.html");
+        std::fs::write(&html_path, include_str!("../core/fixtures/search_machine_learning.html"))
+            .unwrap();
+        let cli = Cli::try_parse_from([
+            "google-patent-cli",
+            "search",
+            "--from-html",
+            html_path.to_str().unwrap(),
+        ])
+        .unwrap();
+        assert!(run_app(cli).await.is_ok());
+    }
+
+    #[test]
+    fn test_render_claims_chart() {
+        let html = include_str!("../core/fixtures/patent_US9152718B2.html");
+        let patent = extract::parse_patent_html(
+            html,
+            "US9152718B2",
+            "https://patents.google.com/patent/US9152718B2/en",
         );
+        let claims = patent.claims.unwrap();
+        let args = |extra: &[&str]| {
+            let cli = Cli::try_parse_from(
+                ["google-patent-cli", "claims", "US9152718B2"].iter().chain(extra),
+            )
+            .unwrap();
+            let Commands::Claims { args } = cli.command else { panic!("expected claims") };
+            args
+        };
+
+        let markdown = render_claims(&claims, &args(&["--chart"])).unwrap();
+        let lines: Vec<&str> = markdown.lines().collect();
+        assert_eq!(lines[0], "| Element | Claim language | Evidence |");
+        assert!(lines[2].starts_with("| 1[pre] | "));
+        assert!(lines[3].starts_with("| 1[a] | "));
+
+        let csv = render_claims(&claims, &args(&["--chart", "--format", "csv"])).unwrap();
+        assert_eq!(csv.lines().next(), Some("element,claim_language,evidence"));
+
+        // Without --chart the parsed claims are printed as JSON
+        let json = render_claims(&claims, &args(&[])).unwrap();
+        let parsed: Vec<Claim> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.len(), claims.len());
+    }
+
+    #[test]
+    fn test_lint_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("claims.json");
+        std::fs::write(&path, r#"["1. A lens.", "2. The lens of claim 3."]"#).unwrap();
+        let report = lint_file(path.to_str().unwrap()).unwrap();
+        assert_eq!(report.patent_id, None);
+        assert_eq!(report.claims_checked, 2);
+        assert_eq!(report.issues.len(), 1);
+        assert_eq!((report.issues[0].claim, report.issues[0].kind), (2, IssueKind::LaterClaim));
+
+        // A saved patent carries its ID into the report
+        std::fs::write(
+            &path,
+            r#"{"id": "US1A", "claims": [{"number": "1", "text": "1. A lens."}]}"#,
+        )
+        .unwrap();
+        let report = lint_file(path.to_str().unwrap()).unwrap();
+        assert_eq!(report.patent_id.as_deref(), Some("US1A"));
+        assert!(report.issues.is_empty());
+
+        std::fs::write(&path, "not json").unwrap();
+        assert!(lint_file(path.to_str().unwrap()).is_err());
     }
 
     #[tokio::test]
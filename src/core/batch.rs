@@ -0,0 +1,180 @@
+use crate::core::cache::{self, CacheMode, PatentCache};
+use crate::core::models::{Patent, SearchOptions};
+use crate::core::patent_search::PatentSearch;
+use crate::core::{Error, Result};
+use futures::stream::{self, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Default number of patents fetched at the same time
+pub const DEFAULT_CONCURRENCY: usize = 4;
+
+/// Most patents an MCP client may have fetched at the same time (each one is a browser tab)
+pub const MAX_CONCURRENCY: usize = 8;
+
+/// Outcome of fetching one patent in a batch
+pub struct BatchItem {
+    pub patent_id: String,
+    pub result: Result<Patent>,
+}
+
+/// A patent that could not be fetched
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct BatchFailure {
+    pub patent_id: String,
+    pub error: String,
+}
+
+/// Summary of a batch fetch
+#[derive(Debug, Clone, Default, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct BatchReport {
+    pub requested: usize,
+    pub succeeded: usize,
+    pub failed: Vec<BatchFailure>,
+    /// Google blocked the batch; the patents after that point were not fetched
+    #[serde(default)]
+    pub rate_limited: bool,
+}
+
+impl BatchReport {
+    pub fn record(&mut self, item: &BatchItem) {
+        match &item.result {
+            Ok(_) => self.succeeded += 1,
+            Err(e) => {
+                self.rate_limited |= matches!(e, Error::RateLimited(_));
+                self.failed
+                    .push(BatchFailure { patent_id: item.patent_id.clone(), error: e.to_string() });
+            }
+        }
+    }
+}
+
+/// Parse a list of patent IDs, one per line or separated by commas/whitespace.
+///
+/// Blank lines and `#` comments are skipped, and duplicates are dropped while
+/// keeping the original order.
+pub fn parse_id_list(text: &str) -> Vec<String> {
+    let mut seen = HashSet::new();
+    text.lines()
+        .map(|line| line.split('#').next().unwrap_or_default())
+        .flat_map(|line| line.split(|c: char| c == ',' || c.is_whitespace()))
+        .filter(|id| !id.is_empty())
+        .filter(|id| seen.insert(id.to_string()))
+        .map(String::from)
+        .collect()
+}
+
+/// File name stem for a patent ID. Only ASCII letters, digits, `-` and `_` are kept,
+/// so an ID taken from user input cannot point outside the output directory.
+pub fn file_stem(patent_id: &str) -> String {
+    patent_id.chars().filter(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_')).collect()
+}
+
+/// Fetch several patents with at most `concurrency` requests in flight.
+///
+/// Each fetch goes through the cache like a single fetch. With the browser
+/// backend every in-flight fetch uses its own tab on the shared browser.
+/// Items are yielded in input order as soon as they are available. Once Google
+/// rate-limits a fetch (after retries), the patents not yet started are skipped
+/// with a rate-limited error instead of being requested.
+pub fn fetch_batch<'a>(
+    searcher: &'a dyn PatentSearch,
+    cache: Option<&'a PatentCache>,
+    patent_ids: Vec<String>,
+    language: Option<String>,
+    mode: CacheMode,
+    concurrency: usize,
+) -> impl Stream<Item = BatchItem> + 'a {
+    let blocked = Arc::new(AtomicBool::new(false));
+    stream::iter(patent_ids)
+        .map(move |patent_id| {
+            let options = SearchOptions {
+                patent_number: Some(patent_id.clone()),
+                language: language.clone(),
+                ..Default::default()
+            };
+            let blocked = blocked.clone();
+            async move {
+                if blocked.load(Ordering::SeqCst) {
+                    let skipped = "Skipped: Google blocked an earlier request in this batch";
+                    let result = Err(Error::RateLimited(skipped.to_string()));
+                    return BatchItem { patent_id, result };
+                }
+                let result =
+                    cache::fetch_patent(searcher, cache, &options, mode).await.and_then(|patent| {
+                        patent.ok_or_else(|| {
+                            Error::Search(format!("No patent found with ID: {}", patent_id))
+                        })
+                    });
+                if matches!(result, Err(Error::RateLimited(_))) {
+                    blocked.store(true, Ordering::SeqCst);
+                }
+                BatchItem { patent_id, result }
+            }
+        })
+        .buffered(concurrency.max(1))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::core::test_support::{MockSearcher, mock_patent};
+    use std::time::Duration;
+
+    #[test]
+    fn test_parse_id_list() {
+        let ids = parse_id_list("US1A\n# comment\n\nUS2B, US3C  # trailing\nUS1A\n");
+        assert_eq!(ids, vec!["US1A", "US2B", "US3C"]);
+    }
+
+    #[test]
+    fn test_file_stem() {
+        assert_eq!(file_stem("US9152718B2"), "US9152718B2");
+        assert_eq!(file_stem("../x"), "x");
+        assert_eq!(file_stem("/etc/passwd"), "etcpasswd");
+        assert_eq!(file_stem("EP-1_2"), "EP-1_2");
+    }
+
+    #[tokio::test]
+    async fn test_fetch_batch_bounded() {
+        let searcher = MockSearcher::new(|id| (id != "NONE").then(|| mock_patent(id)))
+            .with_delay(Duration::from_millis(20));
+        let ids: Vec<String> = ["US1A", "US2A", "NONE", "US4A", "US5A"].map(String::from).to_vec();
+
+        let items: Vec<BatchItem> =
+            fetch_batch(&searcher, None, ids, None, CacheMode::Use, 2).collect().await;
+
+        assert!(searcher.max_in_flight() <= 2);
+        let order: Vec<&str> = items.iter().map(|i| i.patent_id.as_str()).collect();
+        assert_eq!(order, vec!["US1A", "US2A", "NONE", "US4A", "US5A"]);
+
+        let mut report = BatchReport { requested: items.len(), ..Default::default() };
+        items.iter().for_each(|item| report.record(item));
+        assert_eq!(report.succeeded, 4);
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(report.failed[0].patent_id, "NONE");
+        assert!(report.failed[0].error.contains("No patent found"));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_batch_stops_when_rate_limited() {
+        let searcher = MockSearcher::any().rate_limit_after(2);
+        let ids: Vec<String> = (1..=10).map(|i| format!("US{}A", i)).collect();
+
+        let items: Vec<BatchItem> =
+            fetch_batch(&searcher, None, ids, None, CacheMode::Use, 1).collect().await;
+
+        // The remaining patents are skipped instead of being requested
+        assert_eq!(searcher.calls(), 3);
+        assert_eq!(items.len(), 10);
+        let mut report = BatchReport { requested: items.len(), ..Default::default() };
+        items.iter().for_each(|item| report.record(item));
+        assert_eq!(report.succeeded, 2);
+        assert_eq!(report.failed.len(), 8);
+        assert!(report.rate_limited);
+        assert!(report.failed[7].error.contains("Skipped"));
+    }
+}
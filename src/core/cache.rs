@@ -0,0 +1,300 @@
+use crate::core::claims;
+use crate::core::config::Config;
+use crate::core::models::{Patent, SearchOptions};
+use crate::core::patent_search::PatentSearch;
+use crate::core::{Error, Result};
+use directories::ProjectDirs;
+use serde::Deserialize;
+use serde_json::json;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Default time-to-live for cached patents (7 days)
+pub const DEFAULT_CACHE_TTL_HOURS: u64 = 24 * 7;
+
+/// Version of the cached patent format. Bump it when extraction adds or changes
+/// fields, so entries written by older versions are refetched.
+pub const CACHE_VERSION: u32 = 1;
+
+/// How a fetch should interact with the cache
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CacheMode {
+    /// Serve fresh entries from the cache, fetch and store otherwise
+    #[default]
+    Use,
+    /// Always fetch, then update the cache
+    Refresh,
+    /// Serve only from the cache, never fetch (stale entries are allowed)
+    Offline,
+}
+
+impl CacheMode {
+    pub fn from_flags(refresh: bool, offline: bool) -> Self {
+        if offline {
+            Self::Offline
+        } else if refresh {
+            Self::Refresh
+        } else {
+            Self::Use
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct CacheEntry {
+    /// Unix timestamp (seconds) when the patent was fetched
+    fetched_at: u64,
+    /// Entries from before versioning count as version 0
+    #[serde(default)]
+    version: u32,
+    patent: Patent,
+}
+
+/// On-disk cache of fetched patents, keyed by patent ID and language
+pub struct PatentCache {
+    dir: PathBuf,
+    ttl: Duration,
+}
+
+impl PatentCache {
+    pub fn new(dir: impl Into<PathBuf>, ttl: Duration) -> Self {
+        Self { dir: dir.into(), ttl }
+    }
+
+    /// Cache under the platform cache directory, with the TTL from config
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let proj_dirs = ProjectDirs::from("com", "google-patent-cli", "google-patent-cli")
+            .ok_or_else(|| Error::Config("Could not determine cache directory".to_string()))?;
+        let ttl_hours = config.cache_ttl_hours.unwrap_or(DEFAULT_CACHE_TTL_HOURS);
+
+        Ok(Self::new(proj_dirs.cache_dir().join("patents"), Duration::from_secs(ttl_hours * 3600)))
+    }
+
+    fn entry_path(&self, patent_id: &str, language: Option<&str>) -> PathBuf {
+        let sanitize = |s: &str| -> String {
+            s.chars().filter(|c| c.is_ascii_alphanumeric() || *c == '-').collect()
+        };
+        let file_name = match language {
+            Some(lang) => format!("{}.{}.json", sanitize(patent_id), sanitize(lang)),
+            None => format!("{}.json", sanitize(patent_id)),
+        };
+        self.dir.join(file_name)
+    }
+
+    /// Look up a cached patent; entries older than the TTL or [`CACHE_VERSION`] are
+    /// ignored unless `allow_stale`
+    pub fn get(
+        &self,
+        patent_id: &str,
+        language: Option<&str>,
+        allow_stale: bool,
+    ) -> Option<Patent> {
+        let content = fs::read_to_string(self.entry_path(patent_id, language)).ok()?;
+        let entry: CacheEntry = serde_json::from_str(&content).ok()?;
+
+        let age = now_secs().saturating_sub(entry.fetched_at);
+        let outdated = entry.version < CACHE_VERSION;
+        if !allow_stale && (outdated || age > self.ttl.as_secs()) {
+            return None;
+        }
+        let mut patent = entry.patent;
+        if outdated {
+            upgrade(&mut patent);
+        }
+        Some(patent)
+    }
+
+    /// Store a patent. Empty patents (see [`Patent::is_empty`]) are not stored, so
+    /// the next fetch tries again.
+    pub fn put(&self, patent: &Patent, language: Option<&str>) -> Result<()> {
+        if patent.is_empty() {
+            return Ok(());
+        }
+        fs::create_dir_all(&self.dir)?;
+        let entry = json!({ "fetched_at": now_secs(), "version": CACHE_VERSION, "patent": patent });
+        fs::write(self.entry_path(&patent.id, language), serde_json::to_string(&entry)?)?;
+        Ok(())
+    }
+}
+
+/// Fill in what an outdated entry can be given without refetching: the claim tree
+/// (version 1) is derived from the claim texts.
+fn upgrade(patent: &mut Patent) {
+    if let Some(old) = patent.claims.take() {
+        let raw = old.into_iter().map(|c| (c.number, c.id, c.text));
+        patent.claims = Some(claims::parse_claims(raw));
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or_default()
+}
+
+/// Fetch a single patent, going through the cache when one is given.
+///
+/// Returns `Ok(None)` when the searcher finds no patent with the given ID.
+pub async fn fetch_patent(
+    searcher: &dyn PatentSearch,
+    cache: Option<&PatentCache>,
+    options: &SearchOptions,
+    mode: CacheMode,
+) -> Result<Option<Patent>> {
+    let patent_id = options
+        .patent_number
+        .as_deref()
+        .ok_or_else(|| Error::Search("No patent ID given".to_string()))?;
+    let language = options.language.as_deref();
+
+    if let Some(cache) = cache {
+        match mode {
+            CacheMode::Use => {
+                if let Some(patent) = cache.get(patent_id, language, false) {
+                    return Ok(Some(patent));
+                }
+            }
+            CacheMode::Offline => {
+                return cache.get(patent_id, language, true).map(Some).ok_or_else(|| {
+                    Error::Cache(format!("Patent {} is not in the cache (offline mode)", patent_id))
+                });
+            }
+            CacheMode::Refresh => {}
+        }
+    } else if mode == CacheMode::Offline {
+        return Err(Error::Cache("Offline mode requires the cache to be enabled".to_string()));
+    }
+
+    let mut results = searcher.search(options).await?;
+    let patent = results.patents.pop();
+
+    if let (Some(cache), Some(patent)) = (cache, &patent) {
+        cache.put(patent, language)?;
+    }
+
+    Ok(patent)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::core::models::Claim;
+    use crate::core::test_support::MockSearcher;
+
+    fn options(id: &str, language: Option<&str>) -> SearchOptions {
+        SearchOptions {
+            patent_number: Some(id.to_string()),
+            language: language.map(String::from),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fetch_uses_cache() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let cache = PatentCache::new(temp_dir.path(), Duration::from_secs(3600));
+        let searcher = MockSearcher::any();
+        let opts = options("US9152718B2", None);
+
+        let p = fetch_patent(&searcher, Some(&cache), &opts, CacheMode::Use).await.unwrap();
+        assert_eq!(p.unwrap().title, "Mock Patent");
+        let p = fetch_patent(&searcher, Some(&cache), &opts, CacheMode::Use).await.unwrap();
+        assert!(p.is_some());
+        assert_eq!(searcher.calls(), 1);
+
+        // Refresh always fetches
+        fetch_patent(&searcher, Some(&cache), &opts, CacheMode::Refresh).await.unwrap();
+        assert_eq!(searcher.calls(), 2);
+
+        // Offline serves from cache without fetching
+        fetch_patent(&searcher, Some(&cache), &opts, CacheMode::Offline).await.unwrap();
+        assert_eq!(searcher.calls(), 2);
+
+        // Language is part of the key
+        let opts = options("US9152718B2", Some("ja"));
+        let err =
+            fetch_patent(&searcher, Some(&cache), &opts, CacheMode::Offline).await.unwrap_err();
+        assert!(matches!(err, Error::Cache(_)));
+    }
+
+    #[tokio::test]
+    async fn test_expired_entries() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let cache = PatentCache::new(temp_dir.path(), Duration::ZERO);
+        let patent =
+            Patent { id: "US1A".to_string(), title: "Foo".to_string(), ..Default::default() };
+        cache.put(&patent, None).unwrap();
+
+        // Write an entry that is older than the TTL
+        let stale = json!({ "fetched_at": now_secs() - 10, "patent": patent });
+        fs::write(cache.entry_path("US1A", None), stale.to_string()).unwrap();
+
+        assert!(cache.get("US1A", None, false).is_none());
+        assert!(cache.get("US1A", None, true).is_some());
+    }
+
+    #[test]
+    fn test_outdated_entries() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let cache = PatentCache::new(temp_dir.path(), Duration::from_secs(3600));
+        fs::create_dir_all(temp_dir.path()).unwrap();
+
+        // An entry written before versioning, with claims not yet parsed into a tree
+        let claim = |n: &str, text: &str| Claim {
+            number: n.to_string(),
+            text: text.to_string(),
+            ..Default::default()
+        };
+        let patent = Patent {
+            id: "US1A".to_string(),
+            claims: Some(vec![claim("1", "1. A lens."), claim("2", "2. The lens of claim 1.")]),
+            ..Default::default()
+        };
+        let old = json!({ "fetched_at": now_secs(), "patent": patent });
+        fs::write(cache.entry_path("US1A", None), old.to_string()).unwrap();
+
+        // Fetched again when possible...
+        assert!(cache.get("US1A", None, false).is_none());
+        // ...and upgraded when served anyway
+        let claims = cache.get("US1A", None, true).unwrap().claims.unwrap();
+        assert!(claims[0].independent);
+        assert_eq!(claims[0].text, "A lens.");
+        assert!(!claims[1].independent);
+        assert_eq!(claims[1].depends_on, [1]);
+
+        let patent =
+            Patent { id: "US2A".to_string(), title: "Foo".to_string(), ..Default::default() };
+        cache.put(&patent, None).unwrap();
+        assert!(cache.get("US2A", None, false).is_some());
+    }
+
+    #[test]
+    fn test_empty_patent_is_not_cached() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let cache = PatentCache::new(temp_dir.path(), Duration::from_secs(3600));
+
+        let patent =
+            Patent { id: "US1A".to_string(), title: "No Title".to_string(), ..Default::default() };
+        cache.put(&patent, None).unwrap();
+        assert!(cache.get("US1A", None, true).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_outdated_entry_is_refetched() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let cache = PatentCache::new(temp_dir.path(), Duration::from_secs(3600));
+        fs::create_dir_all(temp_dir.path()).unwrap();
+        let searcher = MockSearcher::any();
+        let opts = options("US1A", None);
+
+        // Cached before pdf_url existed: fetched again instead of reporting no PDF
+        let patent = Patent { id: "US1A".to_string(), ..Default::default() };
+        let old = json!({ "fetched_at": now_secs(), "patent": patent });
+        fs::write(cache.entry_path("US1A", None), old.to_string()).unwrap();
+
+        fetch_patent(&searcher, Some(&cache), &opts, CacheMode::Use).await.unwrap();
+        assert_eq!(searcher.calls(), 1);
+        fetch_patent(&searcher, Some(&cache), &opts, CacheMode::Use).await.unwrap();
+        assert_eq!(searcher.calls(), 1);
+    }
+}
@@ -0,0 +1,590 @@
+//! Parse claims into a dependency tree, and split them for claim charts.
+//!
+//! Google Patents gives each claim as text starting with its number ("2. The method of
+//! claim 1, ..."). The number is stripped, and the claims referred to ("claim 1 or 2",
+//! "any one of claims 1 to 5") and what the claim is directed to are read from the wording.
+//!
+//! For charts, independent claims are split into preamble, transitional phrase and
+//! limitation elements with stable IDs: `1[pre]` for the preamble, then `1[a]`, `1[b]`...
+
+use crate::core::models::{Claim, ClaimCategory, ClaimRef};
+use serde::{Deserialize, Serialize};
+
+/// Keywords naming what a claim is directed to
+const CATEGORY_KEYWORDS: &[(ClaimCategory, &[&str])] = &[
+    (
+        ClaimCategory::Crm,
+        &[
+            "readable medium",
+            "readable media",
+            "readable storage",
+            "storage medium",
+            "storage media",
+            "recording medium",
+            "program product",
+            "記録媒体",
+            "記憶媒体",
+        ],
+    ),
+    (ClaimCategory::Method, &["method", "process", "方法"]),
+    (ClaimCategory::System, &["system", "システム"]),
+    (ClaimCategory::Apparatus, &["apparatus", "device", "machine", "equipment", "装置"]),
+];
+
+/// Words ending the preamble of an English claim
+const PREAMBLE_ENDS: &[&str] =
+    &["comprising", "consisting", "including", "having", "wherein", "characterized", ":", ","];
+
+/// Words ending the preamble of a Japanese claim, whose head noun comes last
+const PREAMBLE_ENDS_JA: &[&str] = &["であって", "において"];
+
+/// Words and punctuation between two claim numbers of a range ("1 to 5")
+const RANGE_WORDS: &[&str] = &["to", "through", "thru", "乃至", "ないし", "から"];
+const RANGE_PUNCT: &[char] = &['-', '–', '—', '~', '～'];
+
+/// Words and punctuation between two listed claim numbers ("1 or 2")
+const LIST_WORDS: &[&str] =
+    &["or", "and", "claim", "claims", "又は", "または", "及び", "および", "もしくは"];
+const LIST_PUNCT: &[char] = &[',', '、', '/'];
+
+/// Largest range expanded, so a misread number cannot produce millions of references
+const MAX_RANGE: u32 = 500;
+
+/// Transitional phrases between preamble and body, longest first
+const TRANSITIONS: &[&str] = &[
+    "consisting essentially of",
+    "consisting of",
+    "comprising",
+    "comprises",
+    "including",
+    "includes",
+];
+
+/// A limitation of a charted claim
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ClaimElement {
+    /// Stable element ID, e.g. "1[a]"
+    pub id: String,
+    pub text: String,
+}
+
+/// An independent claim split into preamble, transitional phrase and elements
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ClaimChart {
+    /// Claim number, e.g. "00001"
+    pub number: String,
+    pub category: ClaimCategory,
+    /// Preamble, charted as `<n>[pre]`; empty when the claim has none
+    pub preamble: String,
+    /// "comprising", "consisting of", ... as worded in the claim
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transition: Option<String>,
+    pub elements: Vec<ClaimElement>,
+}
+
+/// Output format of a rendered claim chart
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    schemars::JsonSchema,
+    clap::ValueEnum,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum ChartFormat {
+    /// Markdown table
+    #[default]
+    Markdown,
+    /// Comma-separated values
+    Csv,
+}
+
+/// Parse claims given as `(number, id, text)`, as found on the page.
+pub fn parse_claims(raw: impl IntoIterator<Item = (String, String, String)>) -> Vec<Claim> {
+    let mut claims: Vec<Claim> =
+        raw.into_iter().map(|(number, id, text)| parse_claim(number, id, &text)).collect();
+
+    // Link references to the claims present; claims worded without a category
+    // ("The invention of claim 1") take the category of the claim they refer to
+    for i in 0..claims.len() {
+        let mut dependencies = Vec::new();
+        let mut category = claims[i].category;
+        for n in &claims[i].depends_on {
+            let Some(parent) = claims[..i].iter().find(|c| claim_number(&c.number) == Some(*n))
+            else {
+                continue;
+            };
+            dependencies.push(ClaimRef { number: parent.number.clone(), id: parent.id.clone() });
+            if category == ClaimCategory::Other {
+                category = parent.category;
+            }
+        }
+        claims[i].dependencies = dependencies;
+        claims[i].category = category;
+    }
+    claims
+}
+
+fn parse_claim(number: String, id: String, text: &str) -> Claim {
+    let text = strip_number(text.trim()).to_string();
+    let depends_on = references(&text, claim_number(&number));
+    Claim {
+        independent: depends_on.is_empty(),
+        category: category(&text),
+        number,
+        id,
+        text,
+        depends_on,
+        dependencies: vec![],
+    }
+}
+
+/// Claim number from the page's `num` attribute (e.g., "00002")
+pub(crate) fn claim_number(number: &str) -> Option<u32> {
+    number.trim().parse().ok()
+}
+
+/// Remove the leading "2. " from a claim text.
+fn strip_number(text: &str) -> &str {
+    let rest = text.trim_start_matches(|c: char| c.is_ascii_digit());
+    if rest.len() == text.len() {
+        return text;
+    }
+    match rest.strip_prefix(['.', ')']) {
+        Some(rest) if rest.is_empty() || rest.starts_with(char::is_whitespace) => rest.trim_start(),
+        _ => text,
+    }
+}
+
+/// Claims referred to by `text`, all lower than `own` when the claim's number is known.
+fn references(text: &str, own: Option<u32>) -> Vec<u32> {
+    let mut numbers: Vec<u32> = claim_references(text).iter().map(|r| r.number).collect();
+
+    if let Some(own) = own
+        && ["preceding claim", "previous claim", "foregoing claim"]
+            .iter()
+            .any(|w| normalize(text).contains(w))
+    {
+        numbers.extend(1..own);
+    }
+
+    numbers.retain(|&n| n >= 1 && own.is_none_or(|own| n < own));
+    numbers.sort_unstable();
+    numbers.dedup();
+    numbers
+}
+
+/// A claim number written in a claim text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Reference {
+    /// Character offset of the "claim" the number follows
+    pub offset: usize,
+    pub number: u32,
+}
+
+/// Every claim number written in `text`, whether or not such a claim exists.
+pub(crate) fn claim_references(text: &str) -> Vec<Reference> {
+    let text = normalize(text);
+    let mut refs = Vec::new();
+    for keyword in ["claim", "請求項"] {
+        // Skip words merely containing "claim" ("disclaimer", "claimed")
+        let found = text.match_indices(keyword).filter(|(pos, _)| is_word(&text, *pos, keyword));
+        for (pos, _) in found {
+            let after = &text[pos + keyword.len()..];
+            let offset = text[..pos].chars().count();
+            let numbers = number_list(after.strip_prefix('s').unwrap_or(after));
+            refs.extend(numbers.into_iter().map(|number| Reference { offset, number }));
+        }
+    }
+    refs.sort_by_key(|r| r.offset);
+    refs
+}
+
+/// Lowercase with full-width digits, keeping one character per character of `text`.
+pub(crate) fn normalize(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '０'..='９' => char::from(b'0' + (c as u32 - '０' as u32) as u8),
+            _ => c.to_lowercase().next().unwrap_or(c),
+        })
+        .collect()
+}
+
+/// Numbers listed at the start of `text`: "1", "1 or 2", "1 to 5", "1, 3 and 5".
+fn number_list(text: &str) -> Vec<u32> {
+    let mut tokens = Tokens { text };
+    let Some(Token::Number(mut last)) = tokens.next() else {
+        return vec![];
+    };
+    let mut numbers = vec![last];
+
+    loop {
+        let (mut range, mut listed) = (false, false);
+        let next = loop {
+            match tokens.next() {
+                Some(Token::Number(n)) => break Some(n),
+                Some(Token::Word(w)) if RANGE_WORDS.contains(&w) => range = true,
+                Some(Token::Punct(c)) if RANGE_PUNCT.contains(&c) => range = true,
+                Some(Token::Word(w)) if LIST_WORDS.contains(&w) => listed = true,
+                Some(Token::Punct(c)) if LIST_PUNCT.contains(&c) => listed = true,
+                _ => break None,
+            }
+        };
+        match next {
+            Some(n) if range && n > last && n - last <= MAX_RANGE => numbers.extend(last + 1..=n),
+            Some(n) if listed && !range => numbers.push(n),
+            _ => break,
+        }
+        last = numbers[numbers.len() - 1];
+    }
+    numbers
+}
+
+/// What a claim is directed to, from the head noun of its preamble.
+fn category(text: &str) -> ClaimCategory {
+    let text = text.to_lowercase();
+    let japanese =
+        text.chars().any(|c| matches!(c, '\u{3040}'..='\u{30ff}' | '\u{4e00}'..='\u{9fff}'));
+    let ends = if japanese { PREAMBLE_ENDS_JA } else { PREAMBLE_ENDS };
+    let end = ends.iter().filter_map(|w| text.find(w)).min().unwrap_or(text.len());
+    let preamble = &text[..end];
+
+    let found = CATEGORY_KEYWORDS.iter().flat_map(|(category, words)| {
+        words.iter().flat_map(move |w| {
+            preamble
+                .match_indices(w)
+                .filter(|(pos, _)| is_word(preamble, *pos, w))
+                .map(|(pos, _)| (pos, *category))
+        })
+    });
+    // Japanese names the subject last. English names it first, but media are
+    // introduced by modifiers naming other categories ("machine-readable medium")
+    let head = if japanese {
+        found.max_by_key(|(pos, _)| *pos)
+    } else {
+        found.min_by_key(|(pos, category)| (*category != ClaimCategory::Crm, *pos))
+    };
+    head.map_or(ClaimCategory::Other, |(_, category)| category)
+}
+
+/// Whether `word` found at `pos` stands on its own (plural allowed); non-ASCII words always do.
+pub(crate) fn is_word(text: &str, pos: usize, word: &str) -> bool {
+    if !word.is_ascii() {
+        return true;
+    }
+    let before = text[..pos].chars().next_back();
+    let after = &text[pos + word.len()..];
+    let after = after.strip_prefix('s').unwrap_or(after);
+    !before.is_some_and(char::is_alphabetic)
+        && !after.chars().next().is_some_and(char::is_alphabetic)
+}
+
+/// Split every independent claim for a claim chart.
+pub fn chart_claims(claims: &[Claim]) -> Vec<ClaimChart> {
+    claims.iter().filter(|c| c.independent).map(chart_claim).collect()
+}
+
+/// Split a claim into preamble, transitional phrase and limitation elements.
+pub fn chart_claim(claim: &Claim) -> ClaimChart {
+    let text = claim.text.as_str();
+    // ASCII lowercasing keeps byte offsets valid in `text`
+    let lower = text.to_ascii_lowercase();
+    let transition = TRANSITIONS
+        .iter()
+        .filter_map(|t| {
+            lower
+                .match_indices(t)
+                .find(|(pos, _)| is_word(&lower, *pos, t))
+                .map(|(pos, _)| (pos, t.len()))
+        })
+        .min_by_key(|(pos, _)| *pos);
+    let colon = text.find(':');
+
+    let (preamble, transition, body) = match (transition, colon) {
+        (Some((pos, len)), colon) if colon.is_none_or(|c| pos < c) => {
+            (&text[..pos], Some(&text[pos..pos + len]), &text[pos + len..])
+        }
+        (_, Some(c)) => (&text[..c], None, &text[c + 1..]),
+        _ => ("", None, text),
+    };
+
+    let label = claim_number(&claim.number).map_or_else(|| claim.number.clone(), |n| n.to_string());
+    let elements = body
+        .trim_start_matches([':', ','])
+        .split_inclusive(';')
+        .map(|e| e.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|e| !e.is_empty())
+        .enumerate()
+        .map(|(i, text)| ClaimElement { id: format!("{}[{}]", label, element_letter(i)), text })
+        .collect();
+
+    ClaimChart {
+        number: claim.number.clone(),
+        category: claim.category,
+        preamble: preamble.trim().trim_end_matches(',').trim_end().to_string(),
+        transition: transition.map(String::from),
+        elements,
+    }
+}
+
+/// Letters of the `i`-th element: a..z, then aa, ab...
+fn element_letter(mut i: usize) -> String {
+    let mut letters = Vec::new();
+    loop {
+        letters.push(char::from(b'a' + (i % 26) as u8));
+        if i < 26 {
+            break;
+        }
+        i = i / 26 - 1;
+    }
+    letters.iter().rev().collect()
+}
+
+/// Render charts as a table with an empty column for the evidence mapped to each element.
+pub fn render_chart(charts: &[ClaimChart], format: ChartFormat) -> String {
+    let mut rows = Vec::new();
+    for chart in charts {
+        let label =
+            claim_number(&chart.number).map_or_else(|| chart.number.clone(), |n| n.to_string());
+        if !chart.preamble.is_empty() {
+            let preamble = match &chart.transition {
+                Some(transition) => format!("{} {}", chart.preamble, transition),
+                None => chart.preamble.clone(),
+            };
+            rows.push((format!("{}[pre]", label), preamble));
+        }
+        rows.extend(chart.elements.iter().map(|e| (e.id.clone(), e.text.clone())));
+    }
+
+    let mut out = String::new();
+    match format {
+        ChartFormat::Markdown => {
+            out.push_str("| Element | Claim language | Evidence |\n|---|---|---|\n");
+            for (id, text) in rows {
+                out.push_str(&format!("| {} | {} | |\n", id, text.replace('|', "\\|")));
+            }
+        }
+        ChartFormat::Csv => {
+            out.push_str("element,claim_language,evidence\n");
+            for (id, text) in rows {
+                out.push_str(&format!("{},{},\n", csv_field(&id), csv_field(&text)));
+            }
+        }
+    }
+    out
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+enum Token<'a> {
+    Number(u32),
+    Word(&'a str),
+    Punct(char),
+}
+
+/// Numbers, words and single punctuation characters, skipping whitespace
+struct Tokens<'a> {
+    text: &'a str,
+}
+
+impl<'a> Iterator for Tokens<'a> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Token<'a>> {
+        let text = self.text.trim_start();
+        let first = text.chars().next()?;
+        let len = if first.is_ascii_digit() {
+            text.find(|c: char| !c.is_ascii_digit()).unwrap_or(text.len())
+        } else if first.is_alphabetic() {
+            text.find(|c: char| !c.is_alphabetic()).unwrap_or(text.len())
+        } else {
+            first.len_utf8()
+        };
+        let (token, rest) = text.split_at(len);
+        self.text = rest;
+
+        Some(if first.is_ascii_digit() {
+            Token::Number(token.parse().unwrap_or(0))
+        } else if first.is_alphabetic() {
+            Token::Word(token)
+        } else {
+            Token::Punct(first)
+        })
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn claims(texts: &[&str]) -> Vec<Claim> {
+        parse_claims(texts.iter().enumerate().map(|(i, text)| {
+            (format!("{:05}", i + 1), format!("CLM-{:05}", i + 1), text.to_string())
+        }))
+    }
+
+    #[test]
+    fn test_references() {
+        let cases: &[(&str, &[u32])] = &[
+            ("The method of claim 1, wherein the data set is sampled.", &[1]),
+            ("The device of claim 1 or 2, further comprising a lamp.", &[1, 2]),
+            ("The device according to any one of claims 1 to 5, wherein", &[1, 2, 3, 4, 5]),
+            ("The device of claims 1-3, wherein", &[1, 2, 3]),
+            ("The device of claim 1, 3 and 4, wherein", &[1, 3, 4]),
+            ("The system as claimed in claim 2 or claim 4.", &[2, 4]),
+            ("The method of any preceding claim.", &[1, 2, 3, 4, 5]),
+            ("A method comprising: receiving 3 claims from 2 users.", &[]),
+            ("A disclaimer 5 is shown.", &[]),
+            ("請求項１又は２に記載の装置であって、", &[1, 2]),
+            ("請求項1から3のいずれか一項に記載の方法。", &[1, 2, 3]),
+        ];
+        for (text, expected) in cases {
+            assert_eq!(references(text, Some(6)), *expected, "{}", text);
+        }
+        // A claim cannot refer to itself or to later claims
+        assert_eq!(references("The method of claim 1 or 3.", Some(3)), [1]);
+        assert_eq!(references("The method of claim 7.", None), [7]);
+    }
+
+    #[test]
+    fn test_category() {
+        let cases = [
+            (
+                "A non-transitory machine-readable storage medium storing instructions that, when executed, cause a processor to:",
+                ClaimCategory::Crm,
+            ),
+            ("A computer program product comprising code.", ClaimCategory::Crm),
+            ("A method comprising: receiving a query.", ClaimCategory::Method),
+            ("A system for performing a method, comprising a processor.", ClaimCategory::System),
+            ("An apparatus, comprising: a sensor.", ClaimCategory::Apparatus),
+            ("The device of claim 1, wherein the sensor is optical.", ClaimCategory::Apparatus),
+            ("A compound of formula (I).", ClaimCategory::Other),
+            ("A processor configured to decode a stream.", ClaimCategory::Other),
+            ("データを処理する方法であって、装置が", ClaimCategory::Method),
+            ("請求項1に記載の方法を実行する装置。", ClaimCategory::Apparatus),
+        ];
+        for (text, expected) in cases {
+            assert_eq!(category(text), expected, "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_parse_claims() {
+        let claims = claims(&[
+            "1. A method comprising: receiving a query; and executing the query.",
+            "2. The method of claim 1, wherein the query is sampled.",
+            "3. The invention according to claim 1 or 2, wherein the result is cached.",
+            "4. A system comprising a processor.",
+            "5. A compound as claimed in claim 9.",
+        ]);
+
+        assert_eq!(
+            claims[0].text,
+            "A method comprising: receiving a query; and executing the query."
+        );
+        assert!(claims[0].independent);
+        assert!(claims[0].depends_on.is_empty());
+        assert_eq!(claims[0].category, ClaimCategory::Method);
+
+        assert!(!claims[1].independent);
+        assert_eq!(claims[1].depends_on, [1]);
+        assert_eq!(
+            claims[1].dependencies,
+            [ClaimRef { number: "00001".to_string(), id: "CLM-00001".to_string() }]
+        );
+
+        // No category of its own: inherited from the claims it refers to
+        assert_eq!(claims[2].depends_on, [1, 2]);
+        assert_eq!(claims[2].dependencies.len(), 2);
+        assert_eq!(claims[2].category, ClaimCategory::Method);
+
+        assert!(claims[3].independent);
+        assert_eq!(claims[3].category, ClaimCategory::System);
+
+        // References beyond the claim's own number are ignored
+        assert!(claims[4].independent);
+    }
+
+    #[test]
+    fn test_chart_claims() {
+        let claims = claims(&[
+            "1. A method for analyzing data, comprising: receiving a query; sampling a data set; and executing the query.",
+            "2. The method of claim 1, wherein the query is cached.",
+            "3. A non-transitory machine-readable storage medium storing instructions that, when executed, cause a processor to: receive a query; execute the query.",
+            "4. A composition consisting essentially of water.",
+        ]);
+        let charts = chart_claims(&claims);
+        // Dependent claims are not charted
+        assert_eq!(charts.len(), 3);
+
+        assert_eq!(charts[0].preamble, "A method for analyzing data");
+        assert_eq!(charts[0].transition.as_deref(), Some("comprising"));
+        let elements: Vec<(&str, &str)> =
+            charts[0].elements.iter().map(|e| (e.id.as_str(), e.text.as_str())).collect();
+        assert_eq!(
+            elements,
+            [
+                ("1[a]", "receiving a query;"),
+                ("1[b]", "sampling a data set;"),
+                ("1[c]", "and executing the query.")
+            ]
+        );
+
+        // No transitional phrase: the preamble ends at the colon
+        assert_eq!(
+            charts[1].preamble,
+            "A non-transitory machine-readable storage medium storing instructions that, when executed, cause a processor to"
+        );
+        assert_eq!(charts[1].transition, None);
+        assert_eq!(charts[1].elements[1].id, "3[b]");
+
+        assert_eq!(charts[2].transition.as_deref(), Some("consisting essentially of"));
+        assert_eq!(charts[2].elements[0].text, "water.");
+    }
+
+    #[test]
+    fn test_render_chart() {
+        let claims = claims(&["1. A device comprising: a lens, a sensor; and a | filter."]);
+        let charts = chart_claims(&claims);
+
+        let markdown = render_chart(&charts, ChartFormat::Markdown);
+        let lines: Vec<&str> = markdown.lines().collect();
+        assert_eq!(lines[0], "| Element | Claim language | Evidence |");
+        assert_eq!(lines[2], "| 1[pre] | A device comprising | |");
+        assert_eq!(lines[3], "| 1[a] | a lens, a sensor; | |");
+        assert_eq!(lines[4], "| 1[b] | and a \\| filter. | |");
+
+        let csv = render_chart(&charts, ChartFormat::Csv);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "element,claim_language,evidence");
+        assert_eq!(lines[2], "1[a],\"a lens, a sensor;\",");
+    }
+
+    #[test]
+    fn test_element_letter() {
+        assert_eq!(element_letter(0), "a");
+        assert_eq!(element_letter(25), "z");
+        assert_eq!(element_letter(26), "aa");
+        assert_eq!(element_letter(27), "ab");
+    }
+
+    #[test]
+    fn test_strip_number() {
+        assert_eq!(strip_number("12. A method."), "A method.");
+        assert_eq!(strip_number("1) A method."), "A method.");
+        assert_eq!(strip_number("1,2-dioxane comprising"), "1,2-dioxane comprising");
+        assert_eq!(strip_number("A method."), "A method.");
+    }
+}
@@ -1,3 +1,5 @@
+use crate::core::images::DEFAULT_IMAGE_HOST;
+use crate::core::retry::RetryPolicy;
 use crate::core::{Error, Result};
 use directories::ProjectDirs;
 use serde::{Deserialize, Serialize};
@@ -5,11 +7,39 @@ use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
 
+/// Default Google Patents endpoint
+pub const DEFAULT_BASE_URL: &str = "https://patents.google.com";
+
+/// Backend used to retrieve pages from Google Patents
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Backend {
+    /// Headless Chrome via CDP (renders JavaScript)
+    #[default]
+    Browser,
+    /// Plain HTTP requests parsed in Rust (no browser required)
+    Http,
+}
+
 #[derive(Debug, Serialize, Deserialize, Default, Clone)]
 pub struct Config {
     pub browser_path: Option<PathBuf>,
     #[serde(default)]
     pub chrome_args: Vec<String>,
+    /// Backend to use when none is given on the command line
+    #[serde(default)]
+    pub backend: Backend,
+    /// Base URL for the HTTP backend (defaults to https://patents.google.com)
+    pub base_url: Option<String>,
+    /// How long fetched patents stay fresh in the cache (defaults to 168 hours)
+    pub cache_ttl_hours: Option<u64>,
+    /// Retry policy for page navigation (bot detection, transient failures)
+    #[serde(default)]
+    pub retry: RetryPolicy,
+    /// DevTools URL of a running browser to attach to instead of launching one
+    pub debug_ws_url: Option<String>,
+    /// Host figures and PDFs are downloaded from (defaults to https://patentimages.storage.googleapis.com)
+    pub image_host: Option<String>,
 }
 
 impl Config {
@@ -85,6 +115,16 @@ impl Config {
 
         (path, args)
     }
+
+    /// Base URL for the HTTP backend
+    pub fn base_url(&self) -> &str {
+        self.base_url.as_deref().unwrap_or(DEFAULT_BASE_URL)
+    }
+
+    /// Host figures and PDFs are downloaded from
+    pub fn image_host(&self) -> &str {
+        self.image_host.as_deref().unwrap_or(DEFAULT_IMAGE_HOST)
+    }
 }
 
 /// Detect Chrome/Chromium path from common locations
@@ -167,6 +207,7 @@ mod tests {
         let config = Config {
             browser_path: Some(PathBuf::from("/custom/chrome")),
             chrome_args: vec!["--custom-arg".to_string()],
+            ..Default::default()
         };
 
         let (path, args) = config.resolve();
@@ -174,6 +215,40 @@ mod tests {
         assert_eq!(args, vec!["--custom-arg".to_string()]);
     }
 
+    #[test]
+    fn test_backend_from_toml() {
+        let config: Config = toml::from_str(
+            r#"
+            backend = "http"
+            base_url = "http://127.0.0.1:8080"
+            image_host = "http://127.0.0.1:8081"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.backend, Backend::Http);
+        assert_eq!(config.base_url(), "http://127.0.0.1:8080");
+        assert_eq!(config.image_host(), "http://127.0.0.1:8081");
+
+        let config: Config = toml::from_str("").unwrap();
+        assert_eq!(config.backend, Backend::Browser);
+        assert_eq!(config.base_url(), DEFAULT_BASE_URL);
+        assert_eq!(config.image_host(), DEFAULT_IMAGE_HOST);
+        assert_eq!(config.retry, RetryPolicy::default());
+    }
+
+    #[test]
+    fn test_retry_from_toml() {
+        let config: Config = toml::from_str(
+            r#"
+            [retry]
+            max_retries = 5
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.retry.max_retries, 5);
+        assert_eq!(config.retry.initial_delay_ms, RetryPolicy::default().initial_delay_ms);
+    }
+
     #[test]
     fn test_resolve_auto_detect() {
         // When no config is set, should auto-detect (or return None if not found)
@@ -0,0 +1,387 @@
+//! Citation graph crawler.
+//!
+//! Starting from seed patents, backward citations ("Patent Citations") and forward
+//! citations ("Cited By") are followed breadth-first up to a number of hops in each
+//! direction, fetching at most a fixed number of patents. Backward nodes are only
+//! expanded backward and forward nodes only forward, so the depths stay meaningful.
+
+use crate::core::batch::{self, DEFAULT_CONCURRENCY};
+use crate::core::cache::{CacheMode, PatentCache};
+use crate::core::models::{Citation, Patent};
+use crate::core::patent_search::PatentSearch;
+use crate::core::{Error, Result};
+use chrono::NaiveDate;
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// Largest node budget an MCP client may request
+pub const MAX_NODES: usize = 1000;
+
+/// Limits of a crawl
+#[derive(Debug, Clone)]
+pub struct CrawlOptions {
+    /// Hops followed through backward citations (what the seeds cite)
+    pub backward_depth: usize,
+    /// Hops followed through forward citations (what cites the seeds)
+    pub forward_depth: usize,
+    /// Most patents fetched, seeds included
+    pub max_nodes: usize,
+    pub concurrency: usize,
+    pub language: Option<String>,
+    pub mode: CacheMode,
+}
+
+impl Default for CrawlOptions {
+    fn default() -> Self {
+        Self {
+            backward_depth: 2,
+            forward_depth: 1,
+            max_nodes: 200,
+            concurrency: DEFAULT_CONCURRENCY,
+            language: None,
+            mode: CacheMode::Use,
+        }
+    }
+}
+
+/// How a node was reached from the seeds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Direction {
+    Seed,
+    Backward,
+    Forward,
+}
+
+/// Outgoing `CITES` edge of a node
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct Cites {
+    /// Cited patent
+    pub publication_number: String,
+    #[serde(default)]
+    pub examiner_cited: bool,
+    /// Family-to-family citation
+    #[serde(default)]
+    pub family: bool,
+}
+
+/// A patent in the citation graph
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct CrawlNode {
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assignee: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority_date: Option<NaiveDate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub publication_date: Option<NaiveDate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub legal_status: Option<String>,
+    pub direction: Direction,
+    /// Hops from the nearest seed
+    pub depth: usize,
+    /// Fetched, as opposed to only known from another patent's citations
+    pub fetched: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(rename = "CITES", default, skip_serializing_if = "Vec::is_empty")]
+    pub cites: Vec<Cites>,
+}
+
+impl CrawlNode {
+    fn from_citation(citation: &Citation, direction: Direction, depth: usize) -> Self {
+        Self {
+            id: citation.publication_number.clone(),
+            title: citation.title.clone(),
+            assignee: citation.assignee.clone(),
+            priority_date: citation.priority_date,
+            publication_date: citation.publication_date,
+            legal_status: None,
+            direction,
+            depth,
+            fetched: false,
+            error: None,
+            cites: vec![],
+        }
+    }
+
+    fn update_from(&mut self, patent: &Patent) {
+        self.title = Some(patent.title.clone()).filter(|t| !t.is_empty()).or(self.title.take());
+        self.assignee = patent.assignee.clone().or(self.assignee.take());
+        self.priority_date = patent.priority_date.or(self.priority_date);
+        self.publication_date = patent.publication_date.or(self.publication_date);
+        self.legal_status = patent.legal_status.clone();
+        self.fetched = true;
+    }
+}
+
+/// Directed citation between two nodes of the graph
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct CitationEdge {
+    /// Citing patent
+    pub from: String,
+    /// Cited patent
+    pub to: String,
+    #[serde(default)]
+    pub examiner_cited: bool,
+    #[serde(default)]
+    pub family: bool,
+}
+
+/// Citation graph around the seeds
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct CrawlResult {
+    pub seeds: Vec<String>,
+    /// Number of patents fetched (or attempted)
+    pub fetched: usize,
+    /// Whether the crawl stopped because of the node budget
+    pub budget_exhausted: bool,
+    /// Google blocked the crawl; nodes not fetched by then were not expanded
+    #[serde(default)]
+    pub rate_limited: bool,
+    pub edges: Vec<CitationEdge>,
+    /// Nodes in the order they were reached, each with its outgoing `CITES` edges
+    #[serde(rename = "Patent")]
+    pub nodes: Vec<CrawlNode>,
+}
+
+/// Crawl the citation graph around `seeds`.
+///
+/// Edges are recorded between every pair of nodes in the graph that cite each other,
+/// not only along the paths the crawl followed.
+pub async fn crawl(
+    searcher: &dyn PatentSearch,
+    cache: Option<&PatentCache>,
+    seeds: &[String],
+    options: &CrawlOptions,
+    verbose: bool,
+) -> Result<CrawlResult> {
+    let mut nodes: Vec<CrawlNode> = Vec::new();
+    let mut index: HashMap<String, usize> = HashMap::new();
+    // Citations of the fetched patents, to draw edges once the graph is known
+    let mut citations: Vec<(String, Vec<Citation>, Vec<Citation>)> = Vec::new();
+
+    let mut frontier: Vec<String> = Vec::new();
+    for seed in seeds {
+        if !index.contains_key(seed) {
+            index.insert(seed.clone(), nodes.len());
+            nodes.push(CrawlNode {
+                id: seed.clone(),
+                title: None,
+                assignee: None,
+                priority_date: None,
+                publication_date: None,
+                legal_status: None,
+                direction: Direction::Seed,
+                depth: 0,
+                fetched: false,
+                error: None,
+                cites: vec![],
+            });
+            frontier.push(seed.clone());
+        }
+    }
+
+    let mut fetched = 0;
+    let mut budget_exhausted = false;
+    let mut rate_limited = false;
+    while !frontier.is_empty() {
+        let remaining = options.max_nodes.saturating_sub(fetched);
+        if frontier.len() > remaining {
+            frontier.truncate(remaining);
+            budget_exhausted = true;
+        }
+        if frontier.is_empty() {
+            break;
+        }
+
+        let mut items = std::pin::pin!(batch::fetch_batch(
+            searcher,
+            cache,
+            std::mem::take(&mut frontier),
+            options.language.clone(),
+            options.mode,
+            options.concurrency,
+        ));
+        let mut next = Vec::new();
+        while let Some(item) = items.next().await {
+            fetched += 1;
+            let Some(&i) = index.get(&item.patent_id) else { continue };
+            let patent = match item.result {
+                Ok(patent) => patent,
+                Err(e) => {
+                    if verbose {
+                        eprintln!("Failed to fetch {}: {}", item.patent_id, e);
+                    }
+                    rate_limited |= matches!(e, Error::RateLimited(_));
+                    nodes[i].error = Some(e.to_string());
+                    continue;
+                }
+            };
+            nodes[i].update_from(&patent);
+            let (direction, depth) = (nodes[i].direction, nodes[i].depth);
+
+            let mut expand = |cited: &[Citation], to: Direction, max_depth: usize| {
+                if (direction != Direction::Seed && direction != to) || depth >= max_depth {
+                    return;
+                }
+                for citation in cited {
+                    let id = &citation.publication_number;
+                    if !index.contains_key(id) {
+                        index.insert(id.clone(), nodes.len());
+                        nodes.push(CrawlNode::from_citation(citation, to, depth + 1));
+                        next.push(id.clone());
+                    }
+                }
+            };
+            let backward = patent.citations.unwrap_or_default();
+            let forward = patent.cited_by.unwrap_or_default();
+            expand(&backward, Direction::Backward, options.backward_depth);
+            expand(&forward, Direction::Forward, options.forward_depth);
+            citations.push((item.patent_id, backward, forward));
+        }
+        if verbose {
+            eprintln!("Crawled {} patents, {} to fetch next", fetched, next.len());
+        }
+        // Once Google blocks a fetch, every further hop would be blocked too
+        if rate_limited {
+            break;
+        }
+        frontier = next;
+    }
+
+    let mut edges = Vec::new();
+    let mut seen = HashSet::new();
+    for (id, backward, forward) in &citations {
+        let cited = backward.iter().map(|c| (id.as_str(), c.publication_number.as_str(), c));
+        let citing = forward.iter().map(|c| (c.publication_number.as_str(), id.as_str(), c));
+        for (from, to, citation) in cited.chain(citing) {
+            if from == to || !index.contains_key(from) || !index.contains_key(to) {
+                continue;
+            }
+            if seen.insert((from.to_string(), to.to_string())) {
+                edges.push(CitationEdge {
+                    from: from.to_string(),
+                    to: to.to_string(),
+                    examiner_cited: citation.examiner_cited,
+                    family: citation.family,
+                });
+            }
+        }
+    }
+    for edge in &edges {
+        if let Some(&i) = index.get(&edge.from) {
+            nodes[i].cites.push(Cites {
+                publication_number: edge.to.clone(),
+                examiner_cited: edge.examiner_cited,
+                family: edge.family,
+            });
+        }
+    }
+
+    Ok(CrawlResult { seeds: seeds.to_vec(), fetched, budget_exhausted, rate_limited, edges, nodes })
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::core::test_support::MockSearcher;
+
+    fn citation(id: &str) -> Citation {
+        Citation {
+            publication_number: id.to_string(),
+            priority_date: None,
+            publication_date: None,
+            assignee: None,
+            title: Some(format!("Title of {}", id)),
+            examiner_cited: false,
+            family: false,
+        }
+    }
+
+    /// Searcher over a fixed graph of `(id, cites, cited by)`
+    fn graph() -> MockSearcher {
+        let graph: Vec<(&str, Vec<&str>, Vec<&str>)> = vec![
+            ("S", vec!["B1", "B2"], vec!["F1"]),
+            ("B1", vec!["B11", "B2"], vec!["S"]),
+            ("B2", vec!["B21"], vec!["S", "B1"]),
+            ("B11", vec!["B111"], vec!["B1"]),
+            ("B21", vec![], vec!["B2"]),
+            ("F1", vec!["S"], vec!["F11"]),
+            ("F11", vec!["F1"], vec![]),
+        ];
+        MockSearcher::new(move |id| {
+            graph.iter().find(|(p, _, _)| *p == id).map(|(p, cites, cited_by)| Patent {
+                id: p.to_string(),
+                title: format!("Title of {}", p),
+                citations: Some(cites.iter().map(|c| citation(c)).collect()),
+                cited_by: Some(cited_by.iter().map(|c| citation(c)).collect()),
+                ..Default::default()
+            })
+        })
+        .with_error("BLOCKED", || Error::RateLimited("Sorry...".to_string()))
+    }
+
+    fn ids(result: &CrawlResult) -> Vec<&str> {
+        result.nodes.iter().map(|n| n.id.as_str()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_crawl_depths() {
+        let seeds = vec!["S".to_string()];
+        let result = crawl(&graph(), None, &seeds, &CrawlOptions::default(), false).await.unwrap();
+
+        // Two hops backward, one forward; the third backward hop is never fetched
+        assert_eq!(ids(&result), ["S", "B1", "B2", "F1", "B11", "B21"]);
+        assert_eq!(result.fetched, 6);
+        assert!(!result.budget_exhausted);
+        assert!(result.nodes.iter().all(|n| n.fetched));
+        let f1 = &result.nodes[3];
+        assert_eq!((f1.direction, f1.depth), (Direction::Forward, 1));
+
+        // Edges between known nodes, including the B1 -> B2 shortcut
+        let edges: HashSet<(&str, &str)> =
+            result.edges.iter().map(|e| (e.from.as_str(), e.to.as_str())).collect();
+        assert_eq!(edges.len(), 6);
+        assert!(edges.contains(&("B1", "B2")));
+        assert!(edges.contains(&("F1", "S")));
+        assert!(!edges.contains(&("B11", "B111")));
+        let cites: Vec<&str> =
+            result.nodes[1].cites.iter().map(|c| c.publication_number.as_str()).collect();
+        assert_eq!(cites, ["B11", "B2"]);
+    }
+
+    #[tokio::test]
+    async fn test_crawl_budget_and_errors() {
+        let seeds = vec!["S".to_string(), "MISSING".to_string()];
+        let options = CrawlOptions { max_nodes: 3, ..Default::default() };
+        let result = crawl(&graph(), None, &seeds, &options, false).await.unwrap();
+
+        assert_eq!(result.fetched, 3);
+        assert!(result.budget_exhausted);
+        assert!(!result.rate_limited);
+        assert!(result.nodes[1].error.is_some());
+        // Nodes reached but not fetched keep what the citation told about them
+        let f1 = result.nodes.iter().find(|n| n.id == "F1").unwrap();
+        assert!(!f1.fetched);
+        assert_eq!(f1.title.as_deref(), Some("Title of F1"));
+    }
+
+    #[tokio::test]
+    async fn test_crawl_stops_when_rate_limited() {
+        let seeds = vec!["S".to_string(), "BLOCKED".to_string()];
+        let options = CrawlOptions { concurrency: 1, ..Default::default() };
+        let result = crawl(&graph(), None, &seeds, &options, false).await.unwrap();
+
+        // S was fetched before the block, but its citations are not followed
+        assert!(result.rate_limited);
+        assert_eq!(result.fetched, 2);
+        assert!(result.nodes[0].fetched);
+        assert!(result.nodes[1].error.is_some());
+        assert!(result.nodes[2..].iter().all(|n| !n.fetched));
+    }
+}
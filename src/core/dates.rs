@@ -0,0 +1,159 @@
+//! Date parsing for search filters and extracted patent dates.
+
+use crate::core::{Error, Result};
+use chrono::{Datelike, Days, Local, Months, NaiveDate};
+use serde::{Deserialize, Deserializer};
+
+/// Which end of a shorthand period (`2020`, `2020-06`) a date filter stands for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateBound {
+    /// First day of the period (for `*_after` filters)
+    Start,
+    /// Last day of the period (for `*_before` filters)
+    End,
+}
+
+/// Parse a date filter relative to today.
+///
+/// Accepts `YYYY-MM-DD`, `YYYYMMDD`, `YYYY-MM`, `YYYY` and relative forms
+/// such as `-5y`, `-6m`, `-2w` or `-30d`.
+pub fn parse_date_filter(input: &str, bound: DateBound) -> Result<NaiveDate> {
+    parse_date_filter_at(input, bound, Local::now().date_naive())
+}
+
+pub fn parse_date_filter_at(input: &str, bound: DateBound, today: NaiveDate) -> Result<NaiveDate> {
+    let s = input.trim();
+    let invalid = || {
+        Error::InvalidDate(format!(
+            "'{}' (expected YYYY-MM-DD, YYYY-MM, YYYY or a relative date like -5y)",
+            input
+        ))
+    };
+
+    if let Some(relative) = s.strip_prefix('-') {
+        let unit = relative.chars().last().ok_or_else(invalid)?;
+        let n: u32 = relative[..relative.len() - unit.len_utf8()].parse().map_err(|_| invalid())?;
+        let date = match unit.to_ascii_lowercase() {
+            'y' => today.checked_sub_months(Months::new(n.saturating_mul(12))),
+            'm' => today.checked_sub_months(Months::new(n)),
+            'w' => today.checked_sub_days(Days::new(u64::from(n) * 7)),
+            'd' => today.checked_sub_days(Days::new(u64::from(n))),
+            _ => None,
+        };
+        return date.ok_or_else(invalid);
+    }
+
+    if let Ok(date) = NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+        return Ok(date);
+    }
+    if s.len() == 8
+        && let Ok(date) = NaiveDate::parse_from_str(s, "%Y%m%d")
+    {
+        return Ok(date);
+    }
+
+    let parts: Vec<&str> = s.split('-').collect();
+    let (year, month) = match parts.as_slice() {
+        [year] if year.len() == 4 => (year.parse().map_err(|_| invalid())?, None),
+        [year, month] if year.len() == 4 && month.len() == 2 => (
+            year.parse().map_err(|_| invalid())?,
+            Some(month.parse::<u32>().map_err(|_| invalid())?),
+        ),
+        _ => return Err(invalid()),
+    };
+    let start = NaiveDate::from_ymd_opt(year, month.unwrap_or(1), 1).ok_or_else(invalid)?;
+    match bound {
+        DateBound::Start => Ok(start),
+        DateBound::End => {
+            let months = if month.is_some() { 1 } else { 12 };
+            start
+                .checked_add_months(Months::new(months))
+                .and_then(|next| next.pred_opt())
+                .ok_or_else(invalid)
+        }
+    }
+}
+
+/// Reject a range whose start is later than its end.
+pub fn check_range(name: &str, after: Option<NaiveDate>, before: Option<NaiveDate>) -> Result<()> {
+    match (after, before) {
+        (Some(after), Some(before)) if after > before => Err(Error::InvalidDate(format!(
+            "{} range is inverted: after {} is later than before {}",
+            name, after, before
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Parse an extracted `YYYY-MM-DD` date, ignoring anything else.
+pub fn parse_iso_date(s: &str) -> Option<NaiveDate> {
+    let s = s.trim();
+    let date = NaiveDate::parse_from_str(s, "%Y-%m-%d").ok()?;
+    // chrono accepts unpadded fields and 5-digit years; extracted dates are always padded
+    (s.len() == 10 && date.year() > 0).then_some(date)
+}
+
+/// Deserialize an optional date, treating missing or malformed values (e.g. "Unknown") as `None`.
+pub fn deserialize_lenient<'de, D>(
+    deserializer: D,
+) -> std::result::Result<Option<NaiveDate>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = Option::<serde_json::Value>::deserialize(deserializer)?;
+    Ok(value.as_ref().and_then(|v| v.as_str()).and_then(parse_iso_date))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_parse_date_filter() {
+        let today = date("2026-03-31");
+        let parse = |s: &str, bound| parse_date_filter_at(s, bound, today);
+
+        assert_eq!(parse("2024-01-15", DateBound::Start).unwrap(), date("2024-01-15"));
+        assert_eq!(parse("20240115", DateBound::End).unwrap(), date("2024-01-15"));
+
+        // Shorthands expand to the start or end of the period
+        assert_eq!(parse("2020", DateBound::Start).unwrap(), date("2020-01-01"));
+        assert_eq!(parse("2020", DateBound::End).unwrap(), date("2020-12-31"));
+        assert_eq!(parse("2020-06", DateBound::Start).unwrap(), date("2020-06-01"));
+        assert_eq!(parse("2020-02", DateBound::End).unwrap(), date("2020-02-29"));
+
+        // Relative dates count back from today, clamping to the end of the month
+        assert_eq!(parse("-5y", DateBound::Start).unwrap(), date("2021-03-31"));
+        assert_eq!(parse("-1m", DateBound::Start).unwrap(), date("2026-02-28"));
+        assert_eq!(parse("-2w", DateBound::End).unwrap(), date("2026-03-17"));
+        assert_eq!(parse("-30d", DateBound::End).unwrap(), date("2026-03-01"));
+
+        for bad in ["2024/1/1", "yesterday", "2024-13", "2024-02-30", "-5x", "-y", "24", ""] {
+            let err = parse(bad, DateBound::Start).unwrap_err();
+            assert!(matches!(err, Error::InvalidDate(_)), "{bad}: {err}");
+        }
+    }
+
+    #[test]
+    fn test_check_range() {
+        assert!(check_range("filing", Some(date("2020-01-01")), Some(date("2020-01-01"))).is_ok());
+        assert!(check_range("filing", Some(date("2021-01-01")), None).is_ok());
+        let err = check_range("filing", Some(date("2021-01-01")), Some(date("2020-01-01")));
+        assert_eq!(
+            err.unwrap_err().to_string(),
+            "Invalid date: filing range is inverted: after 2021-01-01 is later than before 2020-01-01"
+        );
+    }
+
+    #[test]
+    fn test_parse_iso_date() {
+        assert_eq!(parse_iso_date("2015-10-06"), Some(date("2015-10-06")));
+        assert_eq!(parse_iso_date("Unknown"), None);
+        assert_eq!(parse_iso_date("2015-1-6"), None);
+    }
+}
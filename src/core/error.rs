@@ -17,6 +17,12 @@ pub enum Error {
     #[error("Search error: {0}")]
     Search(String),
 
+    #[error("Invalid date: {0}")]
+    InvalidDate(String),
+
+    #[error("Rate limited: {0}")]
+    RateLimited(String),
+
     #[error("URL error: {0}")]
     Url(#[from] url::ParseError),
 
@@ -29,6 +35,9 @@ pub enum Error {
     #[error("WebSocket error: {0}")]
     WebSocket(Box<tokio_tungstenite::tungstenite::Error>),
 
+    #[error("Cache error: {0}")]
+    Cache(String),
+
     #[error("MCP error: {0}")]
     Mcp(String),
 
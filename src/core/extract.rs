@@ -0,0 +1,906 @@
+//! Rust-native extraction of patent data from Google Patents HTML.
+//!
+//! Patent pages are always extracted here, whichever backend loaded them: the browser
+//! backend hands over the rendered `outerHTML`, the HTTP backend the server-rendered
+//! page, and `--from-html` a saved page. Search results pages are handled the same
+//! way, from the browser's rendered page or a saved one.
+
+use crate::core::claims;
+use crate::core::dates::parse_iso_date;
+use crate::core::models::{
+    ApplicationInfo, Citation, Claim, Classification, DescriptionParagraph, NonPatentCitation,
+    Patent, PatentImage, SearchResult, SummaryItem, parse_kind_code, parse_result_count,
+};
+use chrono::NaiveDate;
+use scraper::{ElementRef, Html, Selector};
+
+/// Parse a Google Patents patent page into a `Patent`.
+pub fn parse_patent_html(html: &str, patent_id: &str, url: &str) -> Patent {
+    let doc = Html::parse_document(html);
+
+    let title = extract_title(&doc);
+
+    let mut abstract_text = meta_content(&doc, "meta[name='description']")
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty());
+
+    let description_paragraphs = extract_description_paragraphs(&doc);
+
+    // Fallback: if abstract is missing, use first 2 description paragraphs
+    if abstract_text.is_none() && !description_paragraphs.is_empty() {
+        let fallback: Vec<&str> =
+            description_paragraphs.iter().take(2).map(|p| p.text.as_str()).collect();
+        abstract_text = Some(fallback.join("\n"));
+    }
+
+    let claims = extract_claims(&doc);
+    let images = extract_images(&doc);
+    let pdf_url = meta_content(&doc, "meta[name='citation_pdf_url']").or_else(|| {
+        doc.select(&selector("a[itemprop='pdfLink']"))
+            .next()
+            .and_then(|a| a.value().attr("href"))
+            .map(String::from)
+    });
+
+    let filing_date = meta_content(&doc, "meta[name='DC.date'][scheme='dateSubmitted']");
+    let assignee = meta_content(&doc, "meta[name='DC.contributor'][scheme='assignee']")
+        .or_else(|| definition_value(&doc, &["Current Assignee", "Original Assignee", "Assignee"]));
+    let inventors = extract_inventors(&doc);
+
+    let events = extract_events(&doc);
+    let priority_date =
+        doc.select(&selector("time[itemprop='priorityDate']")).next().and_then(time_value);
+    let publication_date = doc
+        .select(&selector("time[itemprop='publicationDate']"))
+        .next()
+        .and_then(time_value)
+        .or_else(|| {
+            events
+                .iter()
+                .find(|e| e.kind == "publication" && e.title.contains(patent_id))
+                .map(|e| e.date.clone())
+        });
+    let grant_date = events.iter().find(|e| e.kind == "granted").map(|e| e.date.clone());
+    // An adjusted expiration (e.g., term extension) overrides the anticipated one
+    let expiration_date = ["adjusted expiration", "expiration"]
+        .iter()
+        .find_map(|label| events.iter().find(|e| e.title.to_lowercase().contains(label)))
+        .map(|e| e.date.clone());
+
+    let claiming_priority = extract_applications(&doc, "tr[itemprop='appsClaimingPriority']");
+    let mut family_applications = extract_applications(&doc, "tr[itemprop='applications']");
+    // Fallback: the worldwide applications timeline
+    if claiming_priority.is_empty() && family_applications.is_empty() {
+        family_applications = extract_timeline_applications(&doc);
+    }
+
+    let related_application = extract_related_application(&doc, &description_paragraphs);
+    let legal_status = extract_legal_status(&doc, patent_id);
+
+    let citations = extract_citations(
+        &doc,
+        "tr[itemprop='backwardReferencesOrig'], tr[itemprop='backwardReferencesFamily']",
+    );
+    let cited_by = extract_citations(
+        &doc,
+        "tr[itemprop='forwardReferencesOrig'], tr[itemprop='forwardReferencesFamily']",
+    );
+    let non_patent_citations = extract_non_patent_citations(&doc);
+    let classifications = extract_classifications(&doc);
+
+    Patent {
+        id: patent_id.to_string(),
+        title,
+        abstract_text,
+        description_paragraphs: non_empty(description_paragraphs),
+        claims: non_empty(claims),
+        images: non_empty(images),
+        pdf_url,
+        snippet: None,
+        description: None,
+        filing_date: filing_date.as_deref().and_then(parse_iso_date),
+        priority_date: priority_date.as_deref().and_then(parse_iso_date),
+        publication_date: publication_date.as_deref().and_then(parse_iso_date),
+        grant_date: grant_date.as_deref().and_then(parse_iso_date),
+        expiration_date: expiration_date.as_deref().and_then(parse_iso_date),
+        assignee,
+        inventors,
+        kind_code: parse_kind_code(patent_id),
+        related_application,
+        claiming_priority: non_empty(claiming_priority),
+        family_applications: non_empty(family_applications),
+        legal_status,
+        classifications: non_empty(classifications),
+        citations: non_empty(citations),
+        cited_by: non_empty(cited_by),
+        non_patent_citations: non_empty(non_patent_citations),
+        url: url.to_string(),
+    }
+}
+
+/// Parse a rendered Google Patents search results page into a `SearchResult`.
+///
+/// Search results are rendered client-side, so this expects HTML saved from a
+/// browser (e.g., the DOM after the results have loaded).
+pub fn parse_search_results_html(html: &str) -> SearchResult {
+    let doc = Html::parse_document(html);
+
+    let total_results = doc
+        .select(&selector("search-results #count span.flex"))
+        .next()
+        .map(element_text)
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| "Unknown".to_string());
+
+    let patents =
+        doc.select(&selector("search-result-item")).map(parse_search_result_item).collect();
+
+    SearchResult {
+        total_count: parse_result_count(&total_results),
+        total_results,
+        top_assignees: non_empty(summary_items(&doc, "data-assignee")),
+        top_cpcs: non_empty(summary_items(&doc, "data-cpc")),
+        patents,
+    }
+}
+
+/// Summary box items (name/percentage) identified by a `state-modifier` data attribute.
+fn summary_items(doc: &Html, data_attr: &str) -> Vec<SummaryItem> {
+    let value_sel = selector(".value");
+    doc.select(&selector(&format!("state-modifier[{}]", data_attr)))
+        .filter_map(|modifier| {
+            let name = modifier.value().attr(data_attr)?.to_string();
+            // The percentage is in a sibling .value element within the name block
+            let percentage = modifier
+                .ancestors()
+                .filter_map(ElementRef::wrap)
+                .find(|el| el.value().classes().any(|c| c == "nameblock"))
+                .and_then(|block| block.select(&value_sel).next())
+                .map(element_text)
+                .unwrap_or_default();
+            (!name.is_empty()).then_some(SummaryItem { name, percentage })
+        })
+        .collect()
+}
+
+fn parse_search_result_item(item: ElementRef<'_>) -> Patent {
+    let title = item
+        .select(&selector(".result-title h3 raw-html span"))
+        .next()
+        .map(element_text)
+        .unwrap_or_else(|| "No Title".to_string());
+
+    // ID: .pdfLink span, then the title link, then any patent-number-like text
+    let id = item
+        .select(&selector(".pdfLink span"))
+        .next()
+        .map(element_text)
+        .filter(|s| !s.is_empty())
+        .or_else(|| {
+            item.select(&selector(".result-title a[href*='/patent/']"))
+                .next()
+                .and_then(|a| a.value().attr("href"))
+                .and_then(|href| href.split("/patent/").nth(1))
+                .map(|rest| rest.chars().take_while(|c| c.is_ascii_alphanumeric()).collect())
+                .filter(|s: &String| !s.is_empty())
+        })
+        .or_else(|| find_patent_number(&element_text(item)))
+        .unwrap_or_else(|| "Unknown".to_string());
+
+    // Snippet is the longest text in the abstract; anything before it is inventor/assignee
+    let texts: Vec<String> = item
+        .select(&selector("div.abstract raw-html span"))
+        .map(element_text)
+        .filter(|t| !t.is_empty())
+        .collect();
+    let mut snippet = None;
+    let mut assignee = None;
+    if let Some((index, _)) = texts.iter().enumerate().rev().max_by_key(|(_, t)| t.chars().count())
+    {
+        snippet = Some(texts[index].clone());
+        if index > 0 {
+            assignee = Some(texts[..index].join(", "));
+        }
+    }
+
+    // Prefer Filed date over Priority date since most users care about filing date
+    let dates = item.select(&selector("h4.dates")).next().map(element_text).unwrap_or_default();
+    let priority_date = date_after_label(&dates, "Priority");
+    let filing_date = date_after_label(&dates, "Filed").or(priority_date);
+
+    Patent {
+        url: format!("https://patents.google.com/patent/{}", id),
+        kind_code: parse_kind_code(&id),
+        id,
+        title,
+        snippet,
+        assignee,
+        filing_date,
+        priority_date,
+        publication_date: date_after_label(&dates, "Published"),
+        grant_date: date_after_label(&dates, "Granted"),
+        ..Default::default()
+    }
+}
+
+/// Find the first `YYYY-MM-DD` date following a label such as "Filed".
+pub(crate) fn date_after_label(text: &str, label: &str) -> Option<NaiveDate> {
+    text.match_indices(label).find_map(|(pos, _)| {
+        let candidate: String = text[pos + label.len()..].trim_start().chars().take(10).collect();
+        parse_iso_date(&candidate)
+    })
+}
+
+/// Find a publication-number-like token (e.g., "US9152718B2") in free text.
+fn find_patent_number(text: &str) -> Option<String> {
+    text.split(|c: char| !c.is_ascii_alphanumeric())
+        .find(|token| {
+            let b = token.as_bytes();
+            if b.len() < 10 || !b[0].is_ascii_uppercase() || !b[1].is_ascii_uppercase() {
+                return false;
+            }
+            let digits = b[2..].iter().take_while(|c| c.is_ascii_digit()).count();
+            let kind = &b[2 + digits..];
+            digits >= 7
+                && match kind {
+                    [k] => k.is_ascii_uppercase(),
+                    [k, d] => k.is_ascii_uppercase() && d.is_ascii_digit(),
+                    _ => false,
+                }
+        })
+        .map(String::from)
+}
+
+/// Build a selector from a static string.
+fn selector(css: &str) -> Selector {
+    Selector::parse(css).unwrap_or_else(|e| panic!("invalid selector {css:?}: {e}"))
+}
+
+/// Text content of an element with whitespace collapsed, similar to `innerText`.
+pub(crate) fn element_text(el: ElementRef<'_>) -> String {
+    let text: String = el.text().collect();
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn meta_content(doc: &Html, css: &str) -> Option<String> {
+    doc.select(&selector(css))
+        .next()
+        .and_then(|el| el.value().attr("content"))
+        .map(|s| s.to_string())
+}
+
+fn non_empty<T>(items: Vec<T>) -> Option<Vec<T>> {
+    if items.is_empty() { None } else { Some(items) }
+}
+
+fn extract_title(doc: &Html) -> String {
+    // document.title is "<ID> - <title> - Google Patents"
+    if let Some(title_el) = doc.select(&selector("title")).next() {
+        let doc_title = element_text(title_el);
+        let parts: Vec<&str> = doc_title.split(" - ").collect();
+        if parts.len() >= 3 {
+            return parts[1..parts.len() - 1].join(" - ").trim().to_string();
+        }
+    }
+
+    meta_content(doc, "meta[name='DC.title']")
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| "No Title".to_string())
+}
+
+fn extract_description_paragraphs(doc: &Html) -> Vec<DescriptionParagraph> {
+    let mut paras: Vec<DescriptionParagraph> = doc
+        .select(&selector("div.description-paragraph[num]"))
+        .map(|el| DescriptionParagraph {
+            number: el.value().attr("num").unwrap_or_default().to_string(),
+            id: el.value().attr("id").unwrap_or_default().to_string(),
+            text: element_text(el),
+        })
+        .collect();
+
+    // Fallback for unstructured description (e.g., Japanese patents)
+    if paras.is_empty()
+        && let Some(desc) = doc.select(&selector("section[itemprop='description']")).next()
+    {
+        let text = element_text(desc);
+        let text = text.strip_prefix("Description").unwrap_or(&text).trim().to_string();
+        if !text.is_empty() {
+            paras.push(DescriptionParagraph {
+                number: "00001".to_string(),
+                id: "DESC-FULL".to_string(),
+                text,
+            });
+        }
+    }
+
+    paras
+}
+
+fn extract_claims(doc: &Html) -> Vec<Claim> {
+    claims::parse_claims(doc.select(&selector("div.claim[num]")).map(|el| {
+        (
+            el.value().attr("num").unwrap_or_default().to_string(),
+            el.value().attr("id").unwrap_or_default().to_string(),
+            element_text(el),
+        )
+    }))
+}
+
+fn extract_images(doc: &Html) -> Vec<PatentImage> {
+    let mut urls: Vec<String> = doc
+        .select(&selector("[itemprop='images'] meta[itemprop='full']"))
+        .filter_map(|el| el.value().attr("content").map(String::from))
+        .collect();
+
+    if urls.is_empty() {
+        urls = doc
+            .select(&selector("img[src*='patentimages']"))
+            .filter_map(|el| el.value().attr("src").map(String::from))
+            .collect();
+    }
+
+    urls.into_iter()
+        .map(|url| {
+            let figure_number = figure_number_from_url(&url);
+            PatentImage { url, figure_number }
+        })
+        .collect()
+}
+
+/// Extract the `D00000` style figure number from an image URL ending in `D<digits>.png`.
+fn figure_number_from_url(url: &str) -> Option<String> {
+    let stem = url.strip_suffix(".png")?;
+    let pos = stem.rfind('D')?;
+    let digits = &stem[pos + 1..];
+    if !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit()) {
+        Some(format!("D{}", digits))
+    } else {
+        None
+    }
+}
+
+/// Value of the `<dd>` following a `<dt>` with one of the given labels.
+fn definition_value(doc: &Html, labels: &[&str]) -> Option<String> {
+    for dt in doc.select(&selector("dt")) {
+        let label = element_text(dt);
+        if labels.iter().any(|l| l.eq_ignore_ascii_case(&label)) {
+            let dd = dt.next_siblings().filter_map(ElementRef::wrap).next()?;
+            if dd.value().name() == "dd" {
+                return Some(element_text(dd));
+            }
+        }
+    }
+    None
+}
+
+/// Inventor names from the citation metadata, falling back to the definition list.
+fn extract_inventors(doc: &Html) -> Vec<String> {
+    let mut inventors: Vec<String> = doc
+        .select(&selector("meta[name='DC.contributor'][scheme='inventor']"))
+        .filter_map(|el| el.value().attr("content"))
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect();
+
+    if inventors.is_empty() {
+        inventors = doc
+            .select(&selector("dd[itemprop='inventor']"))
+            .map(element_text)
+            .filter(|s| !s.is_empty())
+            .collect();
+    }
+
+    inventors
+}
+
+/// Date of a `<time>` element, preferring its `datetime` attribute.
+fn time_value(el: ElementRef<'_>) -> Option<String> {
+    el.value()
+        .attr("datetime")
+        .map(String::from)
+        .or_else(|| Some(element_text(el)))
+        .filter(|s| !s.is_empty())
+}
+
+/// An entry of the "Events" timeline (publication, grant, expiration, ...).
+struct Event {
+    date: String,
+    title: String,
+    kind: String,
+}
+
+fn extract_events(doc: &Html) -> Vec<Event> {
+    let date_sel = selector("time[itemprop='date']");
+    let title_sel = selector("[itemprop='title']");
+    let type_sel = selector("[itemprop='type']");
+
+    doc.select(&selector("[itemprop='events']"))
+        .filter_map(|event| {
+            let text_in =
+                |sel: &Selector| event.select(sel).next().map(element_text).unwrap_or_default();
+            Some(Event {
+                date: event.select(&date_sel).next().and_then(time_value)?,
+                title: text_in(&title_sel),
+                kind: text_in(&type_sel),
+            })
+        })
+        .collect()
+}
+
+fn extract_applications(doc: &Html, row_css: &str) -> Vec<ApplicationInfo> {
+    let app_num_sel = selector("[itemprop='applicationNumber']");
+    let priority_sel = selector("[itemprop='priorityDate']");
+    let filing_sel = selector("[itemprop='filingDate']");
+    let title_sel = selector("[itemprop='title']");
+    let publication_sel = selector("[itemprop='representativePublication']");
+
+    let text_in = |row: ElementRef<'_>, sel: &Selector| {
+        row.select(sel).next().map(element_text).filter(|s| !s.is_empty())
+    };
+
+    doc.select(&selector(row_css))
+        .filter_map(|row| {
+            let application_number = text_in(row, &app_num_sel)?;
+            // Simple heuristic for country code: first 2 chars if they are letters
+            let country_code = application_number
+                .get(..2)
+                .filter(|cc| cc.chars().all(|c| c.is_ascii_uppercase()))
+                .map(String::from);
+            Some(ApplicationInfo {
+                country_code,
+                publication_number: text_in(row, &publication_sel),
+                priority_date: text_in(row, &priority_sel).as_deref().and_then(parse_iso_date),
+                filing_date: text_in(row, &filing_sel).as_deref().and_then(parse_iso_date),
+                title: text_in(row, &title_sel),
+                application_number,
+            })
+        })
+        .collect()
+}
+
+/// Family members from the worldwide applications timeline, for pages without
+/// application tables.
+///
+/// Each member links to `patent/<id>/<lang>` and is followed by a tooltip with its
+/// application number and filing date.
+fn extract_timeline_applications(doc: &Html) -> Vec<ApplicationInfo> {
+    let mut apps: Vec<ApplicationInfo> = Vec::new();
+    for modifier in
+        doc.select(&selector(".application-timeline state-modifier[data-result^='patent/']"))
+    {
+        let Some(id) = modifier
+            .value()
+            .attr("data-result")
+            .and_then(|result| result.split('/').nth(1))
+            .filter(|id| !id.is_empty())
+        else {
+            continue;
+        };
+        let tooltip = modifier
+            .next_siblings()
+            .filter_map(ElementRef::wrap)
+            .next()
+            .filter(|el| el.value().name() == "overlay-tooltip")
+            .map(element_text)
+            .unwrap_or_default();
+        let application_number = tooltip
+            .split_once("Application number:")
+            .and_then(|(_, rest)| rest.split_whitespace().next())
+            .unwrap_or(id)
+            .to_string();
+        if apps.iter().any(|app| app.application_number == application_number) {
+            continue;
+        }
+        apps.push(ApplicationInfo {
+            application_number,
+            country_code: id.get(..2).map(String::from),
+            publication_number: Some(id.to_string()),
+            priority_date: None,
+            filing_date: date_after_label(&tooltip, "Filing date:"),
+            title: None,
+        });
+    }
+    apps
+}
+
+/// Classification codes, one per hierarchy list.
+///
+/// Each `ul[itemprop=classifications]` lists one code's path from the section
+/// down to the leaf code; the leaf carries the description and the "first" flag.
+fn extract_classifications(doc: &Html) -> Vec<Classification> {
+    let code_sel = selector("[itemprop='Code']");
+    let description_sel = selector("[itemprop='Description']");
+    let meta_true = |li: ElementRef<'_>, prop: &str| {
+        li.select(&selector(&format!("meta[itemprop='{}']", prop)))
+            .next()
+            .and_then(|m| m.value().attr("content"))
+            .is_some_and(|v| v == "true")
+    };
+
+    let mut seen = std::collections::HashSet::new();
+    doc.select(&selector("ul[itemprop='classifications']"))
+        .filter_map(|list| {
+            let items: Vec<ElementRef<'_>> = list
+                .children()
+                .filter_map(ElementRef::wrap)
+                .filter(|el| el.value().name() == "li")
+                .collect();
+            let path: Vec<String> = items
+                .iter()
+                .filter_map(|li| li.select(&code_sel).next().map(element_text))
+                .filter(|code| !code.is_empty())
+                .collect();
+            let leaf = items
+                .iter()
+                .find(|li| meta_true(**li, "Leaf"))
+                .or_else(|| items.last())
+                .copied()?;
+            let code = leaf.select(&code_sel).next().map(element_text)?;
+            if code.is_empty() || !seen.insert(code.clone()) {
+                return None;
+            }
+            Some(Classification {
+                description: leaf
+                    .select(&description_sel)
+                    .next()
+                    .map(element_text)
+                    .filter(|s| !s.is_empty()),
+                first: meta_true(leaf, "FirstCode") || meta_true(leaf, "Inventive"),
+                path,
+                code,
+            })
+        })
+        .collect()
+}
+
+/// Rows of the citation tables, e.g. "Patent Citations" or "Cited By".
+///
+/// Rows from the family tables (`*Family` item props) are flagged as family citations.
+fn extract_citations(doc: &Html, row_css: &str) -> Vec<Citation> {
+    let number_sel = selector("[itemprop='publicationNumber']");
+    let examiner_sel = selector("[itemprop='examinerCited']");
+    let priority_sel = selector("[itemprop='priorityDate']");
+    let publication_sel = selector("[itemprop='publicationDate']");
+    let assignee_sel = selector("[itemprop='assigneeOriginal']");
+    let title_sel = selector("[itemprop='title']");
+
+    let text_in = |row: ElementRef<'_>, sel: &Selector| {
+        row.select(sel).next().map(element_text).filter(|s| !s.is_empty())
+    };
+
+    doc.select(&selector(row_css))
+        .filter_map(|row| {
+            let family = row.value().attr("itemprop").is_some_and(|p| p.ends_with("Family"));
+            Some(Citation {
+                publication_number: text_in(row, &number_sel)?,
+                priority_date: text_in(row, &priority_sel).as_deref().and_then(parse_iso_date),
+                publication_date: text_in(row, &publication_sel)
+                    .as_deref()
+                    .and_then(parse_iso_date),
+                assignee: text_in(row, &assignee_sel),
+                title: text_in(row, &title_sel),
+                examiner_cited: text_in(row, &examiner_sel).is_some(),
+                family,
+            })
+        })
+        .collect()
+}
+
+fn extract_non_patent_citations(doc: &Html) -> Vec<NonPatentCitation> {
+    let title_sel = selector("[itemprop='title']");
+    let examiner_sel = selector("[itemprop='examinerCited']");
+
+    doc.select(&selector("tr[itemprop='detailedNonPatentLiterature']"))
+        .filter_map(|row| {
+            let title =
+                row.select(&title_sel).next().map(element_text).filter(|s| !s.is_empty())?;
+            let examiner_cited =
+                row.select(&examiner_sel).next().is_some_and(|el| !element_text(el).is_empty());
+            Some(NonPatentCitation { title, examiner_cited })
+        })
+        .collect()
+}
+
+fn extract_related_application(doc: &Html, paras: &[DescriptionParagraph]) -> Option<String> {
+    // Method 1: "Related Applications" heading followed by its paragraph
+    for h in doc.select(&selector("h2, h3, h4, heading, b, strong")) {
+        let text = element_text(h).to_uppercase();
+        if text.contains("RELATED APPLICATIONS") || text.contains("CROSS-REFERENCE") {
+            return h
+                .next_siblings()
+                .filter_map(ElementRef::wrap)
+                .next()
+                .map(element_text)
+                .filter(|s| !s.is_empty());
+        }
+    }
+
+    // Method 2: continuation/division language at the start of the description
+    let first = paras.first()?;
+    let lower = first.text.to_lowercase();
+    ["division of", "continuation of", "continuation-in-part of"]
+        .iter()
+        .any(|p| lower.contains(p))
+        .then(|| first.text.clone())
+}
+
+fn extract_legal_status(doc: &Html, patent_id: &str) -> Option<String> {
+    // Method 1: current event of the application timeline, then its state label
+    // (rendered pages), then the legal status block (server-rendered pages)
+    let first_text =
+        |css: &str| doc.select(&selector(css)).next().map(element_text).filter(|s| !s.is_empty());
+    if let Some(status) = first_text(".event[current] .title-text")
+        .or_else(|| first_text("state-label"))
+        .or_else(|| first_text("[itemprop='legalStatusIfi'] [itemprop='status']"))
+    {
+        return Some(status);
+    }
+
+    // Method 2: definition list
+    if let Some(status) = definition_value(doc, &["Status", "Legal status"]) {
+        return Some(status);
+    }
+
+    // Method 3: derive from the kind code
+    match parse_kind_code(patent_id)?.chars().next()? {
+        'A' => Some("Pending".to_string()),
+        'B' => Some("Active".to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::core::models::ClaimCategory;
+
+    const PATENT_HTML: &str = r#"<!DOCTYPE html>
+<html><head>
+<title>US9152718B2 - System and method for interactive big data analysis - Google Patents</title>
+<meta name="description" content="A system and method for interactive big data analysis.">
+<meta name="DC.date" content="2013-08-06" scheme="dateSubmitted">
+<meta name="DC.contributor" content="Google LLC" scheme="assignee">
+</head><body>
+<dl><dd itemprop="legalStatusIfi"><span itemprop="status">Active</span></dd></dl>
+<ul><li itemprop="images"><meta itemprop="full" content="https://patentimages.storage.googleapis.com/ab/US09152718-20151006-D00001.png"></li></ul>
+<section itemprop="description">
+<heading>CROSS-REFERENCE TO RELATED APPLICATIONS</heading>
+<div class="description-paragraph" num="0001" id="p-0001">This application is a continuation of
+ U.S. application Ser. No. 13/000,000.</div>
+</section>
+<section itemprop="claims">
+<div class="claim" num="00001" id="CLM-00001"><div class="claim-text">1. A method <b>comprising</b>:
+ <div class="claim-text">receiving data.</div></div></div>
+</section>
+<table><tr itemprop="appsClaimingPriority">
+<td><span itemprop="applicationNumber">US13/960,000</span></td>
+<td itemprop="priorityDate">2012-08-06</td>
+<td itemprop="filingDate">2013-08-06</td>
+<td itemprop="title">System and method</td>
+</tr></table>
+</body></html>"#;
+
+    #[test]
+    fn test_parse_patent_html() {
+        let p = parse_patent_html(
+            PATENT_HTML,
+            "US9152718B2",
+            "https://patents.google.com/patent/US9152718B2",
+        );
+        assert_eq!(p.title, "System and method for interactive big data analysis");
+        assert_eq!(
+            p.abstract_text.as_deref(),
+            Some("A system and method for interactive big data analysis.")
+        );
+        assert_eq!(p.filing_date, NaiveDate::from_ymd_opt(2013, 8, 6));
+        assert_eq!(p.assignee.as_deref(), Some("Google LLC"));
+        assert_eq!(p.legal_status.as_deref(), Some("Active"));
+
+        let paras = p.description_paragraphs.unwrap();
+        assert_eq!(paras[0].number, "0001");
+        assert_eq!(
+            paras[0].text,
+            "This application is a continuation of U.S. application Ser. No. 13/000,000."
+        );
+        assert_eq!(p.related_application.as_deref(), Some(paras[0].text.as_str()));
+
+        let claims = p.claims.unwrap();
+        assert_eq!(claims.len(), 1);
+        assert_eq!(claims[0].id, "CLM-00001");
+        assert_eq!(claims[0].text, "A method comprising: receiving data.");
+
+        let images = p.images.unwrap();
+        assert_eq!(images[0].figure_number.as_deref(), Some("D00001"));
+
+        let priority = p.claiming_priority.unwrap();
+        assert_eq!(priority[0].application_number, "US13/960,000");
+        assert_eq!(priority[0].country_code.as_deref(), Some("US"));
+        assert_eq!(priority[0].priority_date, NaiveDate::from_ymd_opt(2012, 8, 6));
+        assert!(p.family_applications.is_none());
+    }
+
+    #[test]
+    fn test_legal_status_from_kind_code() {
+        let html = "<html><head><title>JP2020000000A - Foo - Google Patents</title></head></html>";
+        let p = parse_patent_html(html, "JP2020000000A", "u");
+        assert_eq!(p.title, "Foo");
+        assert_eq!(p.legal_status.as_deref(), Some("Pending"));
+
+        let p = parse_patent_html(html, "US9152718B2", "u");
+        assert_eq!(p.legal_status.as_deref(), Some("Active"));
+    }
+
+    #[test]
+    fn test_parse_patent_fixture() {
+        let html = include_str!("fixtures/patent_US9152718B2.html");
+        let p =
+            parse_patent_html(html, "US9152718B2", "https://patents.google.com/patent/US9152718B2");
+        assert_eq!(p.title, "System and method for interactive big data analysis");
+        assert_eq!(p.filing_date, NaiveDate::from_ymd_opt(2013, 8, 6));
+        assert_eq!(p.assignee.as_deref(), Some("Google LLC"));
+        assert_eq!(p.legal_status.as_deref(), Some("Active"));
+        assert_eq!(p.inventors, vec!["Jane Doe", "John Roe"]);
+        assert_eq!(p.priority_date, NaiveDate::from_ymd_opt(2012, 8, 6));
+        assert_eq!(p.publication_date, NaiveDate::from_ymd_opt(2015, 10, 6));
+        assert_eq!(p.grant_date, NaiveDate::from_ymd_opt(2015, 10, 6));
+        assert_eq!(p.expiration_date, NaiveDate::from_ymd_opt(2033, 8, 6));
+        assert_eq!(p.kind_code.as_deref(), Some("B2"));
+        assert_eq!(p.description_paragraphs.as_ref().unwrap().len(), 3);
+
+        let classifications = p.classifications.unwrap();
+        assert_eq!(classifications.len(), 2);
+        assert_eq!(classifications[0].code, "G06F16/2453");
+        assert_eq!(classifications[0].description.as_deref(), Some("Query optimisation"));
+        assert!(classifications[0].first);
+        assert_eq!(classifications[0].path, vec!["G", "G06F", "G06F16/24", "G06F16/2453"]);
+        assert_eq!(classifications[1].code, "G06F16/283");
+        assert!(!classifications[1].first);
+
+        let citations = p.citations.unwrap();
+        assert_eq!(citations.len(), 3);
+        assert_eq!(citations[0].publication_number, "US6038561A");
+        assert!(citations[0].examiner_cited);
+        assert!(!citations[0].family);
+        assert_eq!(citations[0].priority_date, NaiveDate::from_ymd_opt(1996, 10, 15));
+        assert_eq!(citations[0].publication_date, NaiveDate::from_ymd_opt(2000, 3, 14));
+        assert_eq!(citations[0].assignee.as_deref(), Some("Manning & Napier Information Services"));
+        assert!(!citations[1].examiner_cited);
+        assert!(citations[2].family);
+
+        let cited_by = p.cited_by.unwrap();
+        assert_eq!(cited_by.len(), 1);
+        assert_eq!(cited_by[0].publication_number, "US10430111B2");
+        assert_eq!(cited_by[0].title.as_deref(), Some("Optimization techniques for queries"));
+
+        let npl = p.non_patent_citations.unwrap();
+        assert_eq!(npl.len(), 2);
+        assert!(npl[0].title.starts_with("Melnik et al."));
+        assert!(npl[0].examiner_cited);
+        assert!(!npl[1].examiner_cited);
+        assert_eq!(p.images.as_ref().unwrap().len(), 2);
+        assert_eq!(
+            p.pdf_url.as_deref(),
+            Some(
+                "https://patentimages.storage.googleapis.com/4d/2b/4c/0d1f6bc2ea2fd5/US9152718.pdf"
+            )
+        );
+        let family = p.family_applications.as_ref().unwrap();
+        assert_eq!(family.len(), 2);
+        assert_eq!(family[0].publication_number.as_deref(), Some("US9152718B2"));
+        assert_eq!(family[1].publication_number.as_deref(), Some("US9720958B2"));
+
+        let claims = p.claims.unwrap();
+        assert_eq!(claims.len(), 3);
+        assert_eq!(claims[1].number, "00002");
+        assert!(
+            claims[1]
+                .text
+                .starts_with("The non-transitory machine-readable storage medium of claim 1")
+        );
+        assert!(claims[0].independent);
+        assert_eq!(claims[0].category, ClaimCategory::Crm);
+        assert_eq!(claims[1].depends_on, [1]);
+        assert_eq!(claims[1].dependencies[0].id, "CLM-00001");
+        assert_eq!(claims[1].category, ClaimCategory::Crm);
+        assert!(claims[2].independent);
+        assert_eq!(claims[2].category, ClaimCategory::Method);
+    }
+
+    #[test]
+    fn test_parse_rendered_ja_fixture() {
+        let html = include_str!("fixtures/patent_JP2019123456A_ja.html");
+        let p = parse_patent_html(
+            html,
+            "JP2019123456A",
+            "https://patents.google.com/patent/JP2019123456A/ja",
+        );
+        assert_eq!(p.title, "学習装置及び学習方法");
+        assert_eq!(p.assignee.as_deref(), Some("ファナック株式会社"));
+        assert_eq!(p.inventors, vec!["山田 太郎", "佐藤 花子"]);
+        assert_eq!(p.filing_date, NaiveDate::from_ymd_opt(2018, 1, 10));
+        // Status of the current timeline event, not derived from the kind code
+        assert_eq!(p.legal_status.as_deref(), Some("Pending"));
+
+        // Unstructured description becomes a single paragraph
+        let paras = p.description_paragraphs.unwrap();
+        assert_eq!(paras.len(), 1);
+        assert!(paras[0].text.starts_with("【技術分野】 本発明は"));
+        assert_eq!(p.claims.unwrap().len(), 2);
+        let images = p.images.unwrap();
+        assert_eq!(images.len(), 2);
+        assert_eq!(images[1].figure_number.as_deref(), Some("D00002"));
+
+        // No application tables: family members come from the worldwide timeline
+        assert!(p.claiming_priority.is_none());
+        let family = p.family_applications.unwrap();
+        assert_eq!(family.len(), 3);
+        assert_eq!(family[0].application_number, "JP2018001234A");
+        assert_eq!(family[0].publication_number.as_deref(), Some("JP2019123456A"));
+        assert_eq!(family[0].filing_date, NaiveDate::from_ymd_opt(2018, 1, 10));
+        assert_eq!(family[1].application_number, "US16/240,123");
+        assert_eq!(family[1].country_code.as_deref(), Some("US"));
+        assert_eq!(family[2].publication_number.as_deref(), Some("DE102019000111A1"));
+    }
+
+    #[test]
+    fn test_legal_status_from_state_label() {
+        let html = "<html><head><title>US1A - Foo - Google Patents</title></head>\
+            <body><state-label>Withdrawn</state-label></body></html>";
+        let p = parse_patent_html(html, "US1A", "u");
+        assert_eq!(p.legal_status.as_deref(), Some("Withdrawn"));
+    }
+
+    #[test]
+    fn test_parse_search_results_fixture() {
+        let html = include_str!("fixtures/search_machine_learning.html");
+        let sr = parse_search_results_html(html);
+        assert_eq!(sr.total_results, "About 1,234 results");
+
+        let assignees = sr.top_assignees.unwrap();
+        assert_eq!(assignees[0].name, "Google LLC");
+        assert_eq!(assignees[0].percentage, "12.3%");
+        let cpcs = sr.top_cpcs.unwrap();
+        assert_eq!(cpcs[0].name, "G06N20/00");
+
+        assert_eq!(sr.patents.len(), 3);
+        let p = &sr.patents[0];
+        assert_eq!(p.id, "US11694122B2");
+        assert_eq!(p.title, "Distributed machine learning systems, apparatus, and methods");
+        assert_eq!(p.assignee.as_deref(), Some("Nant Holdings Ip, Llc"));
+        assert_eq!(p.filing_date, NaiveDate::from_ymd_opt(2016, 7, 18));
+        assert_eq!(p.priority_date, NaiveDate::from_ymd_opt(2016, 7, 17));
+        assert_eq!(p.grant_date, NaiveDate::from_ymd_opt(2023, 7, 4));
+        assert_eq!(p.publication_date, NaiveDate::from_ymd_opt(2023, 7, 4));
+        assert_eq!(p.kind_code.as_deref(), Some("B2"));
+        assert!(p.snippet.as_deref().unwrap().starts_with("A distributed, online"));
+        assert_eq!(p.url, "https://patents.google.com/patent/US11694122B2");
+
+        // ID from the title link when the PDF link is missing
+        assert_eq!(sr.patents[1].id, "DE102018215057B4");
+        // Priority date when no filing date is shown
+        assert_eq!(sr.patents[1].filing_date, NaiveDate::from_ymd_opt(2017, 9, 12));
+        assert!(sr.patents[1].grant_date.is_none());
+        // ID from free text as a last resort
+        assert_eq!(sr.patents[2].id, "JP2019123456A");
+    }
+
+    #[test]
+    fn test_date_after_label() {
+        let dates = "Priority 2016-07-17 • Filed 2016-07-18 • Published 2023-07-04";
+        assert_eq!(date_after_label(dates, "Filed"), NaiveDate::from_ymd_opt(2016, 7, 18));
+        assert_eq!(date_after_label(dates, "Priority"), NaiveDate::from_ymd_opt(2016, 7, 17));
+        assert!(date_after_label(dates, "Granted").is_none());
+    }
+
+    #[test]
+    fn test_figure_number_from_url() {
+        assert_eq!(
+            figure_number_from_url("https://x/US09152718-20151006-D00000.png").as_deref(),
+            Some("D00000")
+        );
+        assert!(figure_number_from_url("https://x/US09152718-20151006.png").is_none());
+        assert!(figure_number_from_url("https://x/D00001.jpg").is_none());
+    }
+}
@@ -0,0 +1,376 @@
+//! Patent family expansion.
+//!
+//! Starting from a seed patent, the applications it claims priority from and its
+//! family applications are resolved to their publications, which are fetched to
+//! tell where the family was granted, where it is still pending and where it was
+//! abandoned.
+
+use crate::core::batch;
+use crate::core::cache::{self, CacheMode, PatentCache};
+use crate::core::models::{ApplicationInfo, Patent, SearchOptions, parse_kind_code};
+use crate::core::patent_search::PatentSearch;
+use crate::core::{Error, Result};
+use chrono::NaiveDate;
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// Where a family member stands
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, schemars::JsonSchema,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum MemberStatus {
+    Unknown,
+    Abandoned,
+    Pending,
+    Granted,
+}
+
+impl MemberStatus {
+    /// Status of a fetched publication, from its legal status, grant date and kind code
+    pub fn of(patent: &Patent) -> Self {
+        let status = patent.legal_status.as_deref().unwrap_or_default().to_lowercase();
+        let kind = parse_kind_code(&patent.id).and_then(|k| k.chars().next());
+        if ["abandon", "withdrawn", "refused", "rejected"].iter().any(|s| status.contains(s)) {
+            Self::Abandoned
+        } else if patent.grant_date.is_some()
+            || matches!(kind, Some('B' | 'C'))
+            || ["active", "expired", "ceased", "granted"].iter().any(|s| status.contains(s))
+        {
+            Self::Granted
+        } else if status.contains("pending") {
+            Self::Pending
+        } else {
+            Self::Unknown
+        }
+    }
+}
+
+/// One application of the family
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct FamilyMember {
+    /// Application number, when known (the seed may only be known by its publication)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub application_number: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub country_code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub publication_number: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority_date: Option<NaiveDate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filing_date: Option<NaiveDate>,
+    pub status: MemberStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub legal_status: Option<String>,
+    /// Why the publication could not be fetched
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl FamilyMember {
+    fn from_application(app: &ApplicationInfo) -> Self {
+        Self {
+            application_number: Some(app.application_number.clone()),
+            country_code: app.country_code.clone(),
+            publication_number: app.publication_number.clone(),
+            priority_date: app.priority_date,
+            filing_date: app.filing_date,
+            status: MemberStatus::Unknown,
+            legal_status: None,
+            error: None,
+        }
+    }
+
+    /// Jurisdiction of the member: the office prefix of its publication or application number
+    fn jurisdiction(&self) -> Option<String> {
+        self.publication_number
+            .as_deref()
+            .and_then(|p| p.get(..2))
+            .filter(|cc| cc.chars().all(|c| c.is_ascii_uppercase()))
+            .map(String::from)
+            .or_else(|| self.country_code.clone())
+    }
+}
+
+/// A patent family and where it stands in each jurisdiction
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct PatentFamily {
+    /// Patent the family was expanded from
+    pub seed: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub earliest_priority_date: Option<NaiveDate>,
+    /// Jurisdictions with at least one granted member
+    pub granted: Vec<String>,
+    /// Jurisdictions with a pending member but no grant
+    pub pending: Vec<String>,
+    /// Jurisdictions where every member was abandoned
+    pub abandoned: Vec<String>,
+    pub members: Vec<FamilyMember>,
+    /// Google blocked a member fetch; the members after it were not fetched
+    #[serde(default)]
+    pub rate_limited: bool,
+    /// Fetched publications, the seed first
+    #[serde(rename = "Patent")]
+    pub patents: Vec<Patent>,
+}
+
+/// Fetch `seed_id` and its family members.
+///
+/// Returns `Ok(None)` when the seed patent does not exist. Members that cannot be
+/// fetched are kept with their error instead of failing the whole family.
+pub async fn fetch_family(
+    searcher: &dyn PatentSearch,
+    cache: Option<&PatentCache>,
+    seed_id: &str,
+    language: Option<String>,
+    mode: CacheMode,
+    concurrency: usize,
+) -> Result<Option<PatentFamily>> {
+    let options = SearchOptions {
+        patent_number: Some(seed_id.to_string()),
+        language: language.clone(),
+        ..Default::default()
+    };
+    let Some(seed) = cache::fetch_patent(searcher, cache, &options, mode).await? else {
+        return Ok(None);
+    };
+
+    // One member per application, preferring the entry that names a publication
+    let mut members: Vec<FamilyMember> = Vec::new();
+    let applications = seed.claiming_priority.iter().chain(&seed.family_applications).flatten();
+    for app in applications {
+        match members
+            .iter_mut()
+            .find(|m| m.application_number.as_deref() == Some(app.application_number.as_str()))
+        {
+            Some(existing) => {
+                if existing.publication_number.is_none() {
+                    existing.publication_number = app.publication_number.clone();
+                }
+            }
+            None => members.push(FamilyMember::from_application(app)),
+        }
+    }
+    if !members.iter().any(|m| m.publication_number.as_deref() == Some(seed.id.as_str())) {
+        members.insert(
+            0,
+            FamilyMember {
+                application_number: None,
+                country_code: None,
+                publication_number: Some(seed.id.clone()),
+                priority_date: seed.priority_date,
+                filing_date: seed.filing_date,
+                status: MemberStatus::Unknown,
+                legal_status: None,
+                error: None,
+            },
+        );
+    }
+
+    let mut to_fetch: Vec<String> =
+        members.iter().filter_map(|m| m.publication_number.clone()).collect();
+    to_fetch.retain(|id| id != &seed.id);
+    to_fetch.sort();
+    to_fetch.dedup();
+
+    let mut fetched: HashMap<String, Result<Patent>> =
+        batch::fetch_batch(searcher, cache, to_fetch, language, mode, concurrency)
+            .map(|item| (item.patent_id, item.result))
+            .collect()
+            .await;
+
+    let mut rate_limited = false;
+    for member in &mut members {
+        let Some(publication) = member.publication_number.clone() else { continue };
+        let patent = if publication == seed.id {
+            &seed
+        } else {
+            match fetched.get(&publication) {
+                Some(Ok(patent)) => patent,
+                Some(Err(e)) => {
+                    rate_limited |= matches!(e, Error::RateLimited(_));
+                    member.error = Some(e.to_string());
+                    continue;
+                }
+                None => continue,
+            }
+        };
+        member.status = MemberStatus::of(patent);
+        member.legal_status = patent.legal_status.clone();
+        member.priority_date = member.priority_date.or(patent.priority_date);
+        member.filing_date = member.filing_date.or(patent.filing_date);
+    }
+
+    // Statuses per jurisdiction: a grant anywhere in a country outweighs pending or
+    // abandoned members, and a country only counts as abandoned if every member was
+    let mut jurisdictions: BTreeMap<String, Vec<MemberStatus>> = BTreeMap::new();
+    for member in &members {
+        if let Some(cc) = member.jurisdiction() {
+            jurisdictions.entry(cc).or_default().push(member.status);
+        }
+    }
+    let with_status = |status| {
+        jurisdictions
+            .iter()
+            .filter(|(_, statuses)| match status {
+                MemberStatus::Abandoned => statuses.iter().all(|s| *s == status),
+                _ => statuses.iter().max() == Some(&status),
+            })
+            .map(|(cc, _)| cc.clone())
+            .collect()
+    };
+
+    let earliest_priority_date =
+        members.iter().filter_map(|m| m.priority_date).chain(seed.priority_date).min();
+
+    let granted = with_status(MemberStatus::Granted);
+    let pending = with_status(MemberStatus::Pending);
+    let abandoned = with_status(MemberStatus::Abandoned);
+
+    let seed_id = seed.id.clone();
+    let mut patents = vec![seed];
+    for member in &members {
+        if let Some(Ok(patent)) = member.publication_number.as_ref().and_then(|p| fetched.remove(p))
+        {
+            patents.push(patent);
+        }
+    }
+
+    Ok(Some(PatentFamily {
+        seed: seed_id,
+        earliest_priority_date,
+        granted,
+        pending,
+        abandoned,
+        members,
+        rate_limited,
+        patents,
+    }))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::core::Error;
+    use crate::core::test_support::MockSearcher;
+
+    fn app(number: &str, publication: Option<&str>, priority: &str) -> ApplicationInfo {
+        ApplicationInfo {
+            application_number: number.to_string(),
+            country_code: number.get(..2).map(String::from),
+            publication_number: publication.map(String::from),
+            priority_date: priority.parse().ok(),
+            filing_date: None,
+            title: None,
+        }
+    }
+
+    /// Searcher over small families seeded by US1B2 and US2B2
+    fn family_searcher() -> MockSearcher {
+        MockSearcher::new(|id| {
+            let patent = match id {
+                "US1B2" => Patent {
+                    legal_status: Some("Active".to_string()),
+                    priority_date: NaiveDate::from_ymd_opt(2013, 5, 1),
+                    claiming_priority: Some(vec![app("US61/000,001", None, "2012-08-06")]),
+                    family_applications: Some(vec![
+                        app("US13/000,001", Some("US1B2"), "2012-08-06"),
+                        app("EP13000001", Some("EP1A1"), "2012-08-06"),
+                        app("JP2013000001", Some("JP1A"), "2012-08-06"),
+                        app("JP2016000001", Some("JP2B2"), "2012-08-06"),
+                        app("CN201300001", Some("CN1A"), "2012-08-06"),
+                    ]),
+                    ..Default::default()
+                },
+                "EP1A1" => {
+                    Patent { legal_status: Some("Withdrawn".to_string()), ..Default::default() }
+                }
+                "JP1A" => {
+                    Patent { legal_status: Some("Pending".to_string()), ..Default::default() }
+                }
+                "JP2B2" => {
+                    Patent { legal_status: Some("Active".to_string()), ..Default::default() }
+                }
+                "US2B2" => Patent {
+                    family_applications: Some(vec![
+                        app("KR1020130000001", Some("KR1A"), "2012-08-06"),
+                        app("KR1020130000002", Some("KR2A"), "2012-08-06"),
+                        app("PCT/EP2013/000001", Some("WO1A1"), "2012-08-06"),
+                    ]),
+                    ..Default::default()
+                },
+                "KR1A" => {
+                    Patent { legal_status: Some("Withdrawn".to_string()), ..Default::default() }
+                }
+                "KR2A" => Patent::default(),
+                _ => return None,
+            };
+            Some(Patent { id: id.to_string(), ..patent })
+        })
+        .with_error("CN1A", || Error::Search("Page failed to load".to_string()))
+        .with_error("WO1A1", || Error::RateLimited("Sorry...".to_string()))
+    }
+
+    #[tokio::test]
+    async fn test_fetch_family() {
+        let family = fetch_family(&family_searcher(), None, "US1B2", None, CacheMode::Use, 2)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(family.seed, "US1B2");
+        assert_eq!(family.earliest_priority_date, NaiveDate::from_ymd_opt(2012, 8, 6));
+        // A grant outweighs a pending application in the same country
+        assert_eq!(family.granted, ["JP", "US"]);
+        assert!(family.pending.is_empty());
+        assert_eq!(family.abandoned, ["EP"]);
+        assert!(!family.rate_limited);
+
+        // The provisional has no publication; the seed's own application is not duplicated
+        assert_eq!(family.members.len(), 6);
+        assert_eq!(family.members[0].publication_number, None);
+        assert_eq!(family.members[0].status, MemberStatus::Unknown);
+        let cn = family.members.iter().find(|m| m.country_code.as_deref() == Some("CN")).unwrap();
+        assert!(cn.error.is_some());
+
+        let ids: Vec<&str> = family.patents.iter().map(|p| p.id.as_str()).collect();
+        assert_eq!(ids, ["US1B2", "EP1A1", "JP1A", "JP2B2"]);
+
+        let missing =
+            fetch_family(&family_searcher(), None, "US404", None, CacheMode::Use, 2).await.unwrap();
+        assert!(missing.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_family_unknown_members_and_rate_limits() {
+        let family = fetch_family(&family_searcher(), None, "US2B2", None, CacheMode::Use, 1)
+            .await
+            .unwrap()
+            .unwrap();
+
+        // KR1A was withdrawn but the status of KR2A is unknown
+        assert!(family.abandoned.is_empty());
+        assert!(family.rate_limited);
+        let wo = family.members.iter().find(|m| m.publication_number.as_deref() == Some("WO1A1"));
+        assert!(wo.unwrap().error.as_deref().unwrap().starts_with("Rate limited"));
+    }
+
+    #[test]
+    fn test_member_status() {
+        let status = |id: &str, legal: Option<&str>| {
+            MemberStatus::of(&Patent {
+                id: id.to_string(),
+                legal_status: legal.map(String::from),
+                ..Default::default()
+            })
+        };
+        assert_eq!(status("US1B2", None), MemberStatus::Granted);
+        assert_eq!(status("US1A1", Some("Expired - Fee Related")), MemberStatus::Granted);
+        assert_eq!(status("US1A1", Some("Pending")), MemberStatus::Pending);
+        assert_eq!(status("US1A1", Some("Abandoned")), MemberStatus::Abandoned);
+        assert_eq!(status("WO1A1", None), MemberStatus::Unknown);
+    }
+}
@@ -0,0 +1,298 @@
+//! Harvest every result of a large search by slicing it into date windows.
+//!
+//! Google Patents only lets you page through the first results of a query, so a
+//! query with more hits is split into smaller date ranges, recursively, until each
+//! window fits under the cap. The windows are then merged and deduplicated by ID.
+
+use crate::core::job::JobSearcher;
+use crate::core::models::{MAX_PAGE_SIZE, SearchOptions, SearchResult};
+use crate::core::patent_search::{PatentSearch, merge_search_page};
+use crate::core::{Error, Result};
+use chrono::{Days, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Most results Google Patents lets you page through for a single query
+pub const MAX_RESULTS_PER_QUERY: usize = 1000;
+
+/// Results requested when probing a window for its size: one full page, which is
+/// kept as the start of the window so small windows need no further request
+const PROBE_SIZE: usize = MAX_PAGE_SIZE;
+
+/// Date filter used to slice a harvest into windows
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum DateField {
+    Priority,
+    #[default]
+    Filing,
+    Publication,
+}
+
+impl DateField {
+    fn range(self, options: &SearchOptions) -> (Option<NaiveDate>, Option<NaiveDate>) {
+        match self {
+            Self::Priority => (options.priority_after, options.priority_before),
+            Self::Filing => (options.filing_after, options.filing_before),
+            Self::Publication => (options.publication_after, options.publication_before),
+        }
+    }
+
+    /// Range to harvest, filling open ends with 1790 (the first US patents) and `today`.
+    pub fn resolve(
+        self,
+        options: &SearchOptions,
+        today: NaiveDate,
+    ) -> Result<(NaiveDate, NaiveDate)> {
+        let (after, before) = self.range(options);
+        let start = after
+            .or(NaiveDate::from_ymd_opt(1790, 1, 1))
+            .ok_or_else(|| Error::Search("Invalid harvest start date".to_string()))?;
+        Ok((start, before.unwrap_or(today)))
+    }
+
+    pub fn set_range(self, options: &mut SearchOptions, after: NaiveDate, before: NaiveDate) {
+        let (a, b) = match self {
+            Self::Priority => (&mut options.priority_after, &mut options.priority_before),
+            Self::Filing => (&mut options.filing_after, &mut options.filing_before),
+            Self::Publication => (&mut options.publication_after, &mut options.publication_before),
+        };
+        *a = Some(after);
+        *b = Some(before);
+    }
+}
+
+/// Collect all results of `options`, splitting its `by` date range into windows
+/// of at most `cap` results. `options.limit` caps the total, if set.
+pub async fn harvest(
+    searcher: &dyn PatentSearch,
+    options: &SearchOptions,
+    by: DateField,
+    cap: usize,
+    verbose: bool,
+) -> Result<SearchResult> {
+    let (start, end) = by.resolve(options, Local::now().date_naive())?;
+
+    let mut sr = SearchResult::default();
+    let mut seen = HashSet::new();
+    // Earliest window last, so it is popped first
+    let mut windows = vec![(start, end)];
+    let mut first = true;
+
+    while let Some((from, to)) = windows.pop() {
+        if options.limit.is_some_and(|limit| sr.patents.len() >= limit) {
+            break;
+        }
+
+        let mut window =
+            SearchOptions { limit: Some(PROBE_SIZE), num: Some(PROBE_SIZE), ..options.clone() };
+        by.set_range(&mut window, from, to);
+        let probe = searcher.search(&window).await?;
+        if first {
+            sr.total_results = probe.total_results.clone();
+            sr.total_count = probe.total_count;
+            sr.top_assignees = probe.top_assignees.clone();
+            sr.top_cpcs = probe.top_cpcs.clone();
+            first = false;
+        }
+
+        let count = probe.total_count.map(|n| usize::try_from(n).unwrap_or(usize::MAX));
+        if let Some(n) = count
+            && n > cap
+            && from < to
+        {
+            let mid = from + Days::new(((to - from).num_days() / 2).unsigned_abs());
+            if verbose {
+                eprintln!("Splitting {} .. {} ({} results)", from, to, n);
+            }
+            if let Some(next) = mid.succ_opt() {
+                windows.push((next, to));
+            }
+            windows.push((from, mid));
+            continue;
+        }
+
+        let want = count.map_or(cap, |n| n.min(cap));
+        if count.map_or(probe.patents.len() == PROBE_SIZE, |n| n > cap) {
+            eprintln!(
+                "Warning: window {} .. {} may have more than {} results; only the first {} are fetched",
+                from, to, cap, cap
+            );
+        }
+        // The probe is the first page of the window; fetch only the pages after it
+        let mut page = probe;
+        if page.patents.len() == PROBE_SIZE && want > PROBE_SIZE {
+            window.start_page = 1;
+            window.limit = Some(want - PROBE_SIZE);
+            let rest = searcher.search(&window).await?;
+            page.patents.extend(rest.patents);
+        }
+        page.patents.truncate(want);
+        let added = merge_search_page(&mut sr, page, &mut seen, false);
+        if verbose {
+            eprintln!("Harvested {} .. {}: {} new (total {})", from, to, added, sr.patents.len());
+        }
+    }
+
+    if let Some(limit) = options.limit {
+        sr.patents.truncate(limit);
+    }
+    Ok(sr)
+}
+
+/// Pin the `by` range of `options` in `job`, so every window (all derived from the
+/// range ends) keeps its checkpoint key when the job is resumed on a later day.
+pub fn pin_range(
+    options: &mut SearchOptions,
+    by: DateField,
+    job: &JobSearcher,
+    today: NaiveDate,
+) -> Result<()> {
+    let (from, to) = job.pinned("harvest range", by.resolve(options, today)?)?;
+    by.set_range(options, from, to);
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::core::models::Patent;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Searcher over a fixed set of (id, filing date) pairs
+    struct CorpusSearcher {
+        corpus: Vec<(String, NaiveDate)>,
+        calls: AtomicUsize,
+    }
+
+    impl CorpusSearcher {
+        fn new(dates: &[NaiveDate]) -> Self {
+            let corpus = dates.iter().enumerate().map(|(i, d)| (format!("US{}A", i), *d)).collect();
+            Self { corpus, calls: AtomicUsize::new(0) }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl PatentSearch for CorpusSearcher {
+        async fn search(&self, options: &SearchOptions) -> Result<SearchResult> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            let matches: Vec<&(String, NaiveDate)> = self
+                .corpus
+                .iter()
+                .filter(|(_, d)| options.filing_after.is_none_or(|a| *d >= a))
+                .filter(|(_, d)| options.filing_before.is_none_or(|b| *d <= b))
+                .collect();
+            Ok(SearchResult {
+                total_results: format!("About {} results", matches.len()),
+                total_count: Some(matches.len() as u64),
+                patents: matches
+                    .iter()
+                    .skip(options.start_page * options.results_per_page())
+                    .take(options.limit.unwrap_or(10))
+                    .map(|(id, d)| Patent {
+                        id: id.clone(),
+                        filing_date: Some(*d),
+                        ..Default::default()
+                    })
+                    .collect(),
+                ..Default::default()
+            })
+        }
+    }
+
+    fn day(n: u64) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2020, 1, 1).unwrap() + Days::new(n)
+    }
+
+    fn options() -> SearchOptions {
+        SearchOptions {
+            query: Some("foo".to_string()),
+            filing_after: Some(day(0)),
+            filing_before: Some(day(365)),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_harvest_splits_windows() {
+        let dates: Vec<NaiveDate> = (0..23).map(|i| day(i * 15)).collect();
+        let searcher = CorpusSearcher::new(&dates);
+
+        let sr = harvest(&searcher, &options(), DateField::Filing, 5, false).await.unwrap();
+
+        let ids: HashSet<&str> = sr.patents.iter().map(|p| p.id.as_str()).collect();
+        assert_eq!(sr.patents.len(), 23);
+        assert_eq!(ids.len(), 23);
+        // Windows are harvested in date order
+        assert!(sr.patents.windows(2).all(|w| w[0].filing_date <= w[1].filing_date));
+        // Summary comes from the whole range
+        assert_eq!(sr.total_count, Some(23));
+        assert!(searcher.calls.load(Ordering::SeqCst) > 1);
+    }
+
+    #[tokio::test]
+    async fn test_harvest_single_query_when_small() {
+        let dates: Vec<NaiveDate> = (0..4).map(day).collect();
+        let searcher = CorpusSearcher::new(&dates);
+
+        let sr = harvest(&searcher, &options(), DateField::Filing, 5, false).await.unwrap();
+        assert_eq!(sr.patents.len(), 4);
+        // The probe already holds every result
+        assert_eq!(searcher.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_harvest_continues_after_probe() {
+        // Over one page but under the cap: the probe page is kept, not fetched again
+        let dates: Vec<NaiveDate> = (0..130).map(|i| day(i % 365)).collect();
+        let searcher = CorpusSearcher::new(&dates);
+
+        let sr = harvest(&searcher, &options(), DateField::Filing, 150, false).await.unwrap();
+        let ids: HashSet<&str> = sr.patents.iter().map(|p| p.id.as_str()).collect();
+        assert_eq!(ids.len(), 130);
+        assert_eq!(searcher.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_harvest_unsplittable_day_and_limit() {
+        // More results on one day than the cap: fetch what we can instead of looping
+        let dates = vec![day(3); 15];
+        let searcher = CorpusSearcher::new(&dates);
+        let sr = harvest(&searcher, &options(), DateField::Filing, 5, false).await.unwrap();
+        assert_eq!(sr.patents.len(), 5);
+
+        let dates: Vec<NaiveDate> = (0..20).map(|i| day(i * 10)).collect();
+        let searcher = CorpusSearcher::new(&dates);
+        let options = SearchOptions { limit: Some(7), ..options() };
+        let sr = harvest(&searcher, &options, DateField::Filing, 5, false).await.unwrap();
+        assert_eq!(sr.patents.len(), 7);
+    }
+
+    #[tokio::test]
+    async fn test_resume_on_later_day_replays_windows() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("job.jsonl");
+        let dates: Vec<NaiveDate> = (0..23).map(|i| day(i * 15)).collect();
+        // No upper bound: the range ends "today"
+        let open = SearchOptions { filing_before: None, ..options() };
+
+        let first = Arc::new(CorpusSearcher::new(&dates));
+        let job = JobSearcher::open(first, &path).unwrap();
+        let mut options = open.clone();
+        pin_range(&mut options, DateField::Filing, &job, day(400)).unwrap();
+        let sr = harvest(&job, &options, DateField::Filing, 5, false).await.unwrap();
+        assert_eq!(sr.patents.len(), 23);
+
+        // Resumed the next day: the pinned range keeps every window key
+        let second = Arc::new(CorpusSearcher::new(&dates));
+        let job = JobSearcher::open(second.clone(), &path).unwrap();
+        let mut options = open;
+        pin_range(&mut options, DateField::Filing, &job, day(401)).unwrap();
+        assert_eq!(options.filing_before, Some(day(400)));
+        let sr = harvest(&job, &options, DateField::Filing, 5, false).await.unwrap();
+        assert_eq!(sr.patents.len(), 23);
+        assert_eq!(second.calls.load(Ordering::SeqCst), 0);
+    }
+}
@@ -0,0 +1,340 @@
+use crate::core::config::DEFAULT_BASE_URL;
+use crate::core::dates::parse_iso_date;
+use crate::core::extract::{element_text, parse_patent_html};
+use crate::core::models::{Patent, SearchOptions, SearchResult, parse_kind_code};
+use crate::core::patent_search::{BOT_DETECTED_MESSAGE, PatentSearch, merge_search_page};
+use crate::core::retry::RetryPolicy;
+use crate::core::{Error, Result};
+use async_trait::async_trait;
+use scraper::Html;
+use serde_json::Value;
+use std::collections::HashSet;
+use url::Url;
+
+/// Browserless backend that fetches Google Patents pages over plain HTTP.
+///
+/// Patent pages are server-rendered and parsed with `core::extract`. Search
+/// results are rendered client-side, so searches use the JSON endpoint
+/// (`/xhr/query`) that the results page itself calls.
+pub struct HttpPatentSearcher {
+    client: reqwest::Client,
+    base_url: String,
+    verbose: bool,
+    retry_policy: RetryPolicy,
+}
+
+#[async_trait]
+impl PatentSearch for HttpPatentSearcher {
+    async fn search(&self, options: &SearchOptions) -> Result<SearchResult> {
+        if let Some(patent_number) = &options.patent_number {
+            self.fetch_patent(options, patent_number).await
+        } else {
+            self.search_pages(options).await
+        }
+    }
+
+    /// Patent pages are server-rendered, so the raw HTML is the response body
+    async fn fetch_raw_html(&self, options: &SearchOptions) -> Result<String> {
+        self.get_text(&self.rebase(&options.to_url()?)).await
+    }
+}
+
+impl HttpPatentSearcher {
+    pub fn new(base_url: &str, verbose: bool) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .user_agent(concat!("google-patent-cli/", env!("CARGO_PKG_VERSION")))
+            .timeout(std::time::Duration::from_secs(30))
+            .build()?;
+
+        Ok(Self {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            verbose,
+            retry_policy: RetryPolicy::default(),
+        })
+    }
+
+    /// Set the retry policy used around requests
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Point a URL built by `SearchOptions` at the configured base URL.
+    fn rebase(&self, url: &str) -> String {
+        match url.strip_prefix(DEFAULT_BASE_URL) {
+            Some(rest) => format!("{}{}", self.base_url, rest),
+            None => url.to_string(),
+        }
+    }
+
+    async fn get_text(&self, url: &str) -> Result<String> {
+        self.get_page(url).await?.ok_or_else(|| {
+            Error::Search(format!("Request to {} failed with status 404 Not Found", url))
+        })
+    }
+
+    /// Body of `url`, or `None` if there is no such page (404)
+    async fn get_page(&self, url: &str) -> Result<Option<String>> {
+        self.retry_policy.retry(self.verbose, || self.get_page_once(url)).await
+    }
+
+    async fn get_page_once(&self, url: &str) -> Result<Option<String>> {
+        if self.verbose {
+            eprintln!("GET {}", url);
+        }
+        let response = self.client.get(url).send().await?;
+
+        // Google answers bot detection with 429 and a "/sorry/" interstitial
+        let status = response.status();
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS
+            || response.url().path().starts_with("/sorry/")
+        {
+            return Err(Error::RateLimited(BOT_DETECTED_MESSAGE.to_string()));
+        }
+        if status == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !status.is_success() {
+            return Err(Error::Search(format!("Request to {} failed with status {}", url, status)));
+        }
+
+        Ok(Some(response.text().await?))
+    }
+
+    async fn fetch_patent(
+        &self,
+        options: &SearchOptions,
+        patent_number: &str,
+    ) -> Result<SearchResult> {
+        // Report the canonical URL, even when fetching from another base URL
+        let url = options.to_url()?;
+        let Some(html) = self.get_page(&self.rebase(&url)).await? else {
+            return Ok(SearchResult {
+                total_results: "0".to_string(),
+                total_count: Some(0),
+                ..Default::default()
+            });
+        };
+
+        if self.verbose {
+            eprintln!("Extracting patent data...");
+        }
+        let patent = parse_patent_html(&html, patent_number, &url);
+
+        Ok(SearchResult {
+            total_results: "1".to_string(),
+            total_count: Some(1),
+            top_assignees: None,
+            top_cpcs: None,
+            patents: vec![patent],
+        })
+    }
+
+    async fn search_pages(&self, options: &SearchOptions) -> Result<SearchResult> {
+        let limit = options.limit.unwrap_or(10);
+        let per_page = options.results_per_page();
+
+        let mut sr = SearchResult::default();
+        let mut seen = HashSet::new();
+        let mut page_index = 0;
+
+        while sr.patents.len() < limit {
+            let page_url = Url::parse(&options.to_page_url(page_index)?)?;
+            let mut xhr_url = Url::parse(&format!("{}/xhr/query", self.base_url))?;
+            xhr_url
+                .query_pairs_mut()
+                .append_pair("url", page_url.query().unwrap_or_default())
+                .append_pair("exp", "");
+
+            let body = self.get_text(xhr_url.as_str()).await?;
+            let value: Value = serde_json::from_str(&body)
+                .map_err(|e| Error::Search(format!("Failed to parse search results: {}", e)))?;
+            let page_result = parse_query_response(&value);
+
+            let page_len = page_result.patents.len();
+            let added = merge_search_page(&mut sr, page_result, &mut seen, page_index == 0);
+
+            if self.verbose {
+                eprintln!("Patents on page {}: {} ({} new)", page_index + 1, page_len, added);
+            }
+
+            if page_len < per_page || added == 0 {
+                break;
+            }
+            page_index += 1;
+        }
+
+        if sr.patents.len() > limit {
+            sr.patents.truncate(limit);
+        }
+
+        Ok(sr)
+    }
+}
+
+/// Convert an `/xhr/query` JSON response into a `SearchResult`.
+fn parse_query_response(value: &Value) -> SearchResult {
+    let results = &value["results"];
+
+    let total_count = results["total_num_results"].as_u64();
+    let total_results = total_count.map(|n| n.to_string()).unwrap_or_else(|| "Unknown".to_string());
+
+    let patents = results["cluster"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|cluster| cluster["result"].as_array())
+        .flatten()
+        .filter_map(|item| {
+            let p = &item["patent"];
+            let id = p["publication_number"].as_str()?.to_string();
+            let text = |key: &str| p[key].as_str().map(strip_html).filter(|s| !s.is_empty());
+            let date = |key: &str| p[key].as_str().and_then(parse_iso_date);
+
+            Some(Patent {
+                title: text("title").unwrap_or_else(|| "No Title".to_string()),
+                snippet: text("snippet"),
+                // Prefer filing date over priority date, as the browser backend does
+                filing_date: date("filing_date").or_else(|| date("priority_date")),
+                priority_date: date("priority_date"),
+                publication_date: date("publication_date"),
+                grant_date: date("grant_date"),
+                assignee: text("assignee"),
+                inventors: text("inventor").into_iter().collect(),
+                kind_code: parse_kind_code(&id),
+                url: format!("{}/patent/{}", DEFAULT_BASE_URL, id),
+                id,
+                ..Default::default()
+            })
+        })
+        .collect();
+
+    SearchResult { total_results, total_count, top_assignees: None, top_cpcs: None, patents }
+}
+
+/// Strip highlighting tags and decode entities in an HTML fragment.
+fn strip_html(fragment: &str) -> String {
+    element_text(Html::parse_fragment(fragment).root_element())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::core::test_support::{self, FakeResponse};
+    use chrono::NaiveDate;
+    use serde_json::json;
+
+    /// Serve a single canned response on a local port and return the base URL.
+    async fn serve_once(body: &'static str) -> String {
+        serve_once_with_status("200 OK", body).await
+    }
+
+    async fn serve_once_with_status(status: &'static str, body: &'static str) -> String {
+        test_support::fake_host(1, move |_, _| FakeResponse::status(status, body)).await.0
+    }
+
+    #[tokio::test]
+    async fn test_fetch_patent_from_local_server() {
+        let base_url = serve_once(
+            r#"<html><head>
+            <title>US9152718B2 - Interactive analysis - Google Patents</title>
+            <meta name="description" content="An abstract.">
+            </head><body></body></html>"#,
+        )
+        .await;
+
+        let searcher = HttpPatentSearcher::new(&base_url, false).unwrap();
+        let options =
+            SearchOptions { patent_number: Some("US9152718B2".to_string()), ..Default::default() };
+        let result = searcher.search(&options).await.unwrap();
+
+        assert_eq!(result.patents.len(), 1);
+        let p = &result.patents[0];
+        assert_eq!(p.id, "US9152718B2");
+        assert_eq!(p.title, "Interactive analysis");
+        assert_eq!(p.abstract_text.as_deref(), Some("An abstract."));
+        assert_eq!(p.url, "https://patents.google.com/patent/US9152718B2");
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited_status() {
+        let base_url = serve_once_with_status("429 Too Many Requests", "Sorry...").await;
+        let searcher = HttpPatentSearcher::new(&base_url, false)
+            .unwrap()
+            .with_retry_policy(RetryPolicy::none());
+        let options =
+            SearchOptions { patent_number: Some("US9152718B2".to_string()), ..Default::default() };
+
+        let err = searcher.search(&options).await.unwrap_err();
+        assert!(matches!(err, Error::RateLimited(_)));
+    }
+
+    #[tokio::test]
+    async fn test_missing_patent_is_not_found() {
+        let base_url = serve_once_with_status("404 Not Found", "Not Found").await;
+        let searcher = HttpPatentSearcher::new(&base_url, false).unwrap();
+        let options =
+            SearchOptions { patent_number: Some("US0000000A".to_string()), ..Default::default() };
+
+        let result = searcher.search(&options).await.unwrap();
+        assert!(result.patents.is_empty());
+        assert_eq!(result.total_count, Some(0));
+    }
+
+    #[test]
+    fn test_rebase() {
+        let searcher = HttpPatentSearcher::new("http://127.0.0.1:9000/", false).unwrap();
+        assert_eq!(
+            searcher.rebase("https://patents.google.com/patent/US1?hl=ja"),
+            "http://127.0.0.1:9000/patent/US1?hl=ja"
+        );
+    }
+
+    #[test]
+    fn test_parse_query_response() {
+        let value = json!({
+            "results": {
+                "total_num_results": 1234,
+                "cluster": [{
+                    "result": [{
+                        "patent": {
+                            "publication_number": "US11694122B2",
+                            "title": "Distributed <b>machine learning</b> systems",
+                            "snippet": "A distributed, online machine learning system &hellip;",
+                            "priority_date": "2016-07-17",
+                            "filing_date": "2016-07-18",
+                            "grant_date": "2023-07-04",
+                            "inventor": "Patrick Soon-Shiong",
+                            "assignee": "Google LLC"
+                        }
+                    }, {
+                        "patent": {
+                            "publication_number": "JP2020000000A",
+                            "title": "Foo",
+                            "priority_date": "2019-01-01"
+                        }
+                    }]
+                }]
+            }
+        });
+
+        let sr = parse_query_response(&value);
+        assert_eq!(sr.total_results, "1234");
+        assert_eq!(sr.patents.len(), 2);
+        assert_eq!(sr.patents[0].id, "US11694122B2");
+        assert_eq!(sr.patents[0].title, "Distributed machine learning systems");
+        assert_eq!(
+            sr.patents[0].snippet.as_deref(),
+            Some("A distributed, online machine learning system …")
+        );
+        assert_eq!(sr.patents[0].filing_date, NaiveDate::from_ymd_opt(2016, 7, 18));
+        assert_eq!(sr.patents[0].priority_date, NaiveDate::from_ymd_opt(2016, 7, 17));
+        assert_eq!(sr.patents[0].grant_date, NaiveDate::from_ymd_opt(2023, 7, 4));
+        assert_eq!(sr.patents[0].inventors, vec!["Patrick Soon-Shiong"]);
+        assert_eq!(sr.patents[0].kind_code.as_deref(), Some("B2"));
+        assert_eq!(sr.patents[0].url, "https://patents.google.com/patent/US11694122B2");
+        assert_eq!(sr.patents[1].filing_date, NaiveDate::from_ymd_opt(2019, 1, 1));
+    }
+}
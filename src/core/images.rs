@@ -0,0 +1,198 @@
+//! Download patent figures (and other files served by the image host).
+//!
+//! Figures are saved as `<dir>/<patent_id>/<figure>.<ext>` next to a
+//! `manifest.json` mapping figure numbers to the files. Files already present are
+//! skipped, and responses that are not images are rejected.
+
+use crate::core::batch;
+use crate::core::models::Patent;
+use crate::core::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use url::Url;
+
+/// Default host serving patent figures
+pub const DEFAULT_IMAGE_HOST: &str = "https://patentimages.storage.googleapis.com";
+
+/// Name of the manifest written next to the figures
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// A figure that could not be downloaded
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ImageFailure {
+    pub url: String,
+    pub error: String,
+}
+
+/// Figures downloaded for one patent
+#[derive(Debug, Clone, Default, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ImageManifest {
+    pub patent_id: String,
+    /// Figure number (e.g., "D00001") to file name, relative to the manifest
+    pub figures: BTreeMap<String, String>,
+    pub downloaded: usize,
+    /// Figures already present on disk
+    pub skipped: usize,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub failed: Vec<ImageFailure>,
+}
+
+/// Move an image URL onto `host`, keeping its path.
+fn rebase_url(url: &str, host: &str) -> Result<String> {
+    let url = Url::parse(url)?;
+    Ok(format!("{}{}", host.trim_end_matches('/'), url.path()))
+}
+
+/// Figure key and file name for an image URL, e.g. ("D00001", "D00001.png").
+fn file_name(url: &str, figure_number: Option<&str>) -> Option<(String, String)> {
+    let last = url.rsplit('/').next().filter(|s| !s.is_empty())?;
+    let (stem, ext) = last.rsplit_once('.').unwrap_or((last, "png"));
+    let key = figure_number.unwrap_or(stem).to_string();
+    let file = format!("{}.{}", key, ext.to_ascii_lowercase());
+    Some((key, file))
+}
+
+/// Directory the figures of `patent_id` are saved into
+pub fn image_dir(dir: &Path, patent_id: &str) -> PathBuf {
+    dir.join(batch::file_stem(patent_id))
+}
+
+/// Download every figure of `patent` into `<dir>/<patent_id>/` and write the manifest.
+///
+/// Figures that fail are listed in the manifest instead of failing the download.
+pub async fn download_images(patent: &Patent, dir: &Path, host: &str) -> Result<ImageManifest> {
+    let target = image_dir(dir, &patent.id);
+    tokio::fs::create_dir_all(&target).await?;
+    let client = reqwest::Client::builder().timeout(std::time::Duration::from_secs(60)).build()?;
+
+    let mut manifest = ImageManifest { patent_id: patent.id.clone(), ..Default::default() };
+    for image in patent.images.iter().flatten() {
+        let Some((key, file)) = file_name(&image.url, image.figure_number.as_deref()) else {
+            continue;
+        };
+        let path = target.join(&file);
+        if tokio::fs::metadata(&path).await.is_ok_and(|m| m.len() > 0) {
+            manifest.skipped += 1;
+            manifest.figures.insert(key, file);
+            continue;
+        }
+        match download(&client, &image.url, host, &path, "image/").await {
+            Ok(()) => {
+                manifest.downloaded += 1;
+                manifest.figures.insert(key, file);
+            }
+            Err(e) => {
+                manifest.failed.push(ImageFailure { url: image.url.clone(), error: e.to_string() })
+            }
+        }
+    }
+
+    let json = serde_json::to_string_pretty(&manifest)?;
+    tokio::fs::write(target.join(MANIFEST_FILE), json).await?;
+    Ok(manifest)
+}
+
+/// Download `url` from `host` into `path`, rejecting content types not starting with `expected`.
+pub(crate) async fn download(
+    client: &reqwest::Client,
+    url: &str,
+    host: &str,
+    path: &Path,
+    expected: &str,
+) -> Result<()> {
+    let response = client.get(rebase_url(url, host)?).send().await?.error_for_status()?;
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+    if !content_type.starts_with(expected) {
+        return Err(Error::Other(format!(
+            "Expected {}, got content type '{}'",
+            expected, content_type
+        )));
+    }
+    let bytes = response.bytes().await?;
+
+    // Write to a temporary file first so an interrupted download is never skipped later
+    let partial = path.with_extension("part");
+    tokio::fs::write(&partial, &bytes).await?;
+    tokio::fs::rename(&partial, path).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::core::models::PatentImage;
+    use crate::core::test_support::{self, FakeResponse};
+
+    /// Fake image host answering `count` requests: PNGs for `D00000`, HTML for anything else.
+    async fn fake_host(count: usize) -> (String, tokio::task::JoinHandle<Vec<String>>) {
+        test_support::fake_host(count, |line, _| {
+            if line.contains("D00000") {
+                FakeResponse::ok("image/png", "\u{89}PNG")
+            } else {
+                FakeResponse::ok("text/html", "<html>Sorry...</html>")
+            }
+        })
+        .await
+    }
+
+    fn patent() -> Patent {
+        let image = |figure: &str| PatentImage {
+            url: format!("{}/bb/US09152718-20151006-{}.png", DEFAULT_IMAGE_HOST, figure),
+            figure_number: Some(figure.to_string()),
+        };
+        Patent {
+            id: "US9152718B2".to_string(),
+            images: Some(vec![image("D00000"), image("D00001")]),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_file_name_and_rebase() {
+        let url = "https://patentimages.storage.googleapis.com/bb/US09152718-20151006-D00001.png";
+        assert_eq!(
+            file_name(url, Some("D00001")).unwrap(),
+            ("D00001".to_string(), "D00001.png".to_string())
+        );
+        assert_eq!(file_name(url, None).unwrap().0, "US09152718-20151006-D00001");
+        assert_eq!(
+            rebase_url(url, "http://127.0.0.1:8080/").unwrap(),
+            "http://127.0.0.1:8080/bb/US09152718-20151006-D00001.png"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_download_images() {
+        let dir = tempfile::tempdir().unwrap();
+        let (host, server) = fake_host(3).await;
+
+        let manifest = download_images(&patent(), dir.path(), &host).await.unwrap();
+        assert_eq!(manifest.downloaded, 1);
+        assert_eq!(manifest.figures["D00000"], "D00000.png");
+        // An HTML page (e.g., bot detection) is not saved as a figure
+        assert_eq!(manifest.failed.len(), 1);
+        assert!(manifest.failed[0].error.contains("text/html"));
+        let target = dir.path().join("US9152718B2");
+        assert!(target.join("D00000.png").exists());
+        assert!(!target.join("D00001.png").exists());
+
+        // Figures already present are skipped; only the failed one is requested again
+        let manifest = download_images(&patent(), dir.path(), &host).await.unwrap();
+        assert_eq!((manifest.downloaded, manifest.skipped), (0, 1));
+        let written: ImageManifest =
+            serde_json::from_str(&std::fs::read_to_string(target.join(MANIFEST_FILE)).unwrap())
+                .unwrap();
+        assert_eq!(written.skipped, 1);
+
+        let requests = server.await.unwrap();
+        assert_eq!(requests.len(), 3);
+        assert!(requests[2].contains("D00001"));
+    }
+}
@@ -0,0 +1,184 @@
+//! Resumable jobs for long harvests and batch fetches.
+//!
+//! A job wraps a `PatentSearch` and appends every completed `search` call to a
+//! checkpoint file (JSON Lines), keyed by the request URL and limit: one line per
+//! harvested window or fetched patent. When a job is resumed, recorded calls are
+//! answered from the checkpoint, so only the remaining work hits Google Patents.
+//! Settings resolved against the clock (such as an open harvest range) are pinned
+//! in the same file, so a resume on a later day replays the same calls.
+
+use crate::core::models::{SearchOptions, SearchResult};
+use crate::core::patent_search::PatentSearch;
+use crate::core::{Error, Result};
+use async_trait::async_trait;
+use directories::ProjectDirs;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// One completed search call
+#[derive(Serialize, Deserialize)]
+struct Checkpoint {
+    key: String,
+    result: Value,
+}
+
+/// Checkpoint file for a job: a path, or a name stored under the platform cache directory.
+pub fn job_path(job: &str) -> Result<PathBuf> {
+    if job.contains(['/', '\\']) || job.ends_with(".jsonl") {
+        return Ok(PathBuf::from(job));
+    }
+    let name: String =
+        job.chars().filter(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_')).collect();
+    if name.is_empty() {
+        return Err(Error::Config(format!("Invalid job name: {}", job)));
+    }
+    let proj_dirs = ProjectDirs::from("com", "google-patent-cli", "google-patent-cli")
+        .ok_or_else(|| Error::Config("Could not determine cache directory".to_string()))?;
+    Ok(proj_dirs.cache_dir().join("jobs").join(format!("{}.jsonl", name)))
+}
+
+/// Searcher that checkpoints completed calls and replays them on resume
+pub struct JobSearcher {
+    inner: Arc<dyn PatentSearch>,
+    completed: Mutex<HashMap<String, Value>>,
+    file: Mutex<File>,
+}
+
+impl JobSearcher {
+    /// Open (or start) the job at `path`, loading the calls it already completed.
+    pub fn open(inner: Arc<dyn PatentSearch>, path: &Path) -> Result<Self> {
+        // A line cut short by a crash is skipped and simply redone
+        let content = fs::read_to_string(path).unwrap_or_default();
+        let completed = content
+            .lines()
+            .filter_map(|line| serde_json::from_str::<Checkpoint>(line).ok())
+            .map(|c| (c.key, c.result))
+            .collect();
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        if !content.is_empty() && !content.ends_with('\n') {
+            file.write_all(b"\n")?;
+        }
+        Ok(Self { inner, completed: Mutex::new(completed), file: Mutex::new(file) })
+    }
+
+    /// Number of calls recorded in the checkpoint
+    pub fn completed(&self) -> usize {
+        self.completed.lock().map(|c| c.len()).unwrap_or_default()
+    }
+
+    /// Value pinned under `key` by an earlier run, or `value`, pinned now.
+    pub fn pinned<T: Serialize + DeserializeOwned>(&self, key: &str, value: T) -> Result<T> {
+        let key = format!("pin {}", key);
+        let recorded = self.completed.lock().ok().and_then(|c| c.get(&key).cloned());
+        if let Some(recorded) = recorded {
+            return Ok(serde_json::from_value(recorded)?);
+        }
+        self.record(key, serde_json::to_value(&value)?)?;
+        Ok(value)
+    }
+
+    fn record(&self, key: String, result: Value) -> Result<()> {
+        let checkpoint = Checkpoint { key, result };
+        let mut line = serde_json::to_string(&checkpoint)?;
+        line.push('\n');
+        {
+            let mut file =
+                self.file.lock().map_err(|_| Error::Other("Job file lock poisoned".to_string()))?;
+            file.write_all(line.as_bytes())?;
+            file.flush()?;
+        }
+        if let Ok(mut completed) = self.completed.lock() {
+            completed.insert(checkpoint.key, checkpoint.result);
+        }
+        Ok(())
+    }
+}
+
+/// Identify a search call by everything that changes its results
+fn request_key(options: &SearchOptions) -> Result<String> {
+    let limit = options.limit.map_or_else(|| "default".to_string(), |l| l.to_string());
+    Ok(format!("{} limit={}", options.to_page_url(0)?, limit))
+}
+
+#[async_trait]
+impl PatentSearch for JobSearcher {
+    async fn search(&self, options: &SearchOptions) -> Result<SearchResult> {
+        let key = request_key(options)?;
+        let recorded = self.completed.lock().ok().and_then(|c| c.get(&key).cloned());
+        if let Some(result) = recorded {
+            return Ok(serde_json::from_value(result)?);
+        }
+
+        let result = self.inner.search(options).await?;
+        self.record(key, serde_json::to_value(&result)?)?;
+        Ok(result)
+    }
+
+    async fn fetch_raw_html(&self, options: &SearchOptions) -> Result<String> {
+        self.inner.fetch_raw_html(options).await
+    }
+
+    async fn shutdown(&self) {
+        self.inner.shutdown().await;
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::core::test_support::MockSearcher;
+
+    fn fetch(id: &str) -> SearchOptions {
+        SearchOptions { patent_number: Some(id.to_string()), ..Default::default() }
+    }
+
+    #[tokio::test]
+    async fn test_resume_replays_completed_calls() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("job.jsonl");
+        let ids = ["US1A", "US2A", "US3A"];
+
+        // The first run dies on the third patent
+        let first = Arc::new(MockSearcher::any().rate_limit_after(2));
+        let job = JobSearcher::open(first.clone(), &path).unwrap();
+        assert!(job.search(&fetch(ids[0])).await.is_ok());
+        assert!(job.search(&fetch(ids[1])).await.is_ok());
+        assert!(job.search(&fetch(ids[2])).await.is_err());
+
+        // Simulate a line cut short by a crash
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(b"{\"key\":\"trunc").unwrap();
+
+        // The resumed run only fetches what is left
+        let second = Arc::new(MockSearcher::any());
+        let job = JobSearcher::open(second.clone(), &path).unwrap();
+        assert_eq!(job.completed(), 2);
+        for id in ids {
+            let result = job.search(&fetch(id)).await.unwrap();
+            assert_eq!(result.patents[0].id, id);
+        }
+        assert_eq!(second.calls(), 1);
+
+        // Records written after the truncated line are still readable
+        let job = JobSearcher::open(second, &path).unwrap();
+        assert_eq!(job.completed(), 3);
+    }
+
+    #[test]
+    fn test_job_path() {
+        assert_eq!(job_path("./run.jsonl").unwrap(), PathBuf::from("./run.jsonl"));
+        assert!(job_path("lidar-2024").unwrap().ends_with("jobs/lidar-2024.jsonl"));
+        assert!(job_path("..").is_err());
+    }
+}
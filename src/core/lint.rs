@@ -0,0 +1,465 @@
+//! Lint claims for problems commonly raised in examination.
+//!
+//! Checks antecedent basis ("the X" with no earlier "a X" in the claim or the claims it
+//! depends on), references to missing or later claims, multiple dependent claims and
+//! wording that may invoke 35 U.S.C. 112(f). Every issue is tied to a claim number and
+//! a character offset into the claim's text (without the leading claim number).
+//! Antecedent basis is not checked for claims referring to missing or later claims.
+
+use crate::core::claims::{self, claim_number, is_word, normalize};
+use crate::core::models::Claim;
+use crate::core::{Error, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeSet, HashSet};
+
+/// Words introducing a new term ("a sensor", "one or more processors")
+const INDEFINITE: &[&[&str]] = &[
+    &["a"],
+    &["an"],
+    &["another"],
+    &["one", "or", "more"],
+    &["at", "least", "one", "of"],
+    &["at", "least", "one"],
+    &["plurality", "of"],
+];
+
+/// Words referring back to a term ("the sensor", "said processors")
+const DEFINITE: &[&str] = &["the", "said"];
+
+/// Quantifiers skipped after a definite article ("the one or more processors")
+const QUANTIFIERS: &[&[&str]] = &[
+    &["one", "or", "more"],
+    &["at", "least", "one", "of"],
+    &["at", "least", "one"],
+    &["plurality", "of"],
+];
+
+/// Definite terms that never need an antecedent
+const EXEMPT: &[&str] =
+    &["same", "following", "preceding", "foregoing", "above", "other", "claim", "claims"];
+
+/// Words ending a term
+const STOP_WORDS: &str = "a an the said of to for in on at by with from into onto and or that \
+    which wherein whereby when where is are was were be being been has have \
+    having comprising comprises including includes configured adapted \
+    operable based according as than such via using so not each further \
+    stored storing";
+
+/// Longest term compared, in words
+const MAX_TERM_WORDS: usize = 4;
+
+/// Wording that may invoke 35 U.S.C. 112(f)
+const MEANS_PLUS_FUNCTION: &[&str] = &["means for", "means to", "step for"];
+
+/// Generic placeholders treated like "means" when followed by a function
+const NONCE_WORDS: &[&str] = &["module", "unit", "mechanism", "element", "component", "member"];
+const FUNCTION_WORDS: &[&str] = &["for", "configured to", "adapted to", "operable to"];
+
+/// What a lint issue is about
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueKind {
+    /// "the X" without an earlier "a X"
+    AntecedentBasis,
+    /// Refers to a claim that does not exist
+    MissingClaim,
+    /// Refers to itself or to a later claim
+    LaterClaim,
+    /// Depends on more than one claim
+    MultipleDependent,
+    /// Means-plus-function or similar wording
+    MeansPlusFunction,
+}
+
+/// A problem found in a claim
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ClaimIssue {
+    pub claim: u32,
+    pub kind: IssueKind,
+    /// Character offset into the claim text
+    pub offset: usize,
+    pub message: String,
+}
+
+/// Issues found in a set of claims
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct LintReport {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub patent_id: Option<String>,
+    pub claims_checked: usize,
+    pub issues: Vec<ClaimIssue>,
+}
+
+/// A word of a claim text with its character offset
+struct Word<'a> {
+    text: &'a str,
+    offset: usize,
+    /// Punctuation other than a hyphen comes right after the word
+    ends_phrase: bool,
+}
+
+/// Lint `claims`, e.g. `Patent.claims` or claims read with [`claims_from_json`].
+pub fn lint_claims(patent_id: Option<String>, claims: &[Claim]) -> LintReport {
+    let numbers: Vec<u32> = claims
+        .iter()
+        .enumerate()
+        .map(|(i, c)| claim_number(&c.number).unwrap_or(i as u32 + 1))
+        .collect();
+    let index_of = |n: u32| numbers.iter().position(|&m| m == n);
+
+    let words: Vec<Vec<Word<'_>>> = claims.iter().map(|c| words(&c.text)).collect();
+    let introduced: Vec<Vec<(usize, Vec<String>)>> =
+        words.iter().map(|w| introduced_terms(w)).collect();
+
+    let mut issues = Vec::new();
+    for (i, claim) in claims.iter().enumerate() {
+        let own = numbers[i];
+        let resolved = check_references(claim, own, &numbers, &mut issues);
+
+        let parents = claim_parents(claim, &index_of);
+        if parents.len() > 1 {
+            let listed: Vec<String> = parents.iter().map(|&p| numbers[p].to_string()).collect();
+            let mut message = format!("Multiple dependent claim (claims {})", listed.join(", "));
+            if let Some(&p) = parents.iter().find(|&&p| claims[p].depends_on.len() > 1) {
+                message.push_str(&format!(
+                    "; depends on claim {}, itself a multiple dependent claim",
+                    numbers[p]
+                ));
+            }
+            let offset = claims::claim_references(&claim.text).first().map_or(0, |r| r.offset);
+            issues.push(ClaimIssue {
+                claim: own,
+                kind: IssueKind::MultipleDependent,
+                offset,
+                message,
+            });
+        }
+
+        check_means_plus_function(own, &claim.text, &mut issues);
+        if !resolved {
+            continue;
+        }
+
+        // Terms introduced by every claim this claim depends on, directly or not
+        let mut available: Vec<&Vec<String>> = Vec::new();
+        let mut seen = HashSet::new();
+        let mut stack = parents;
+        while let Some(p) = stack.pop() {
+            if seen.insert(p) {
+                available.extend(introduced[p].iter().map(|(_, term)| term));
+                stack.extend(claim_parents(&claims[p], &index_of));
+            }
+        }
+        check_antecedents(own, &words[i], &introduced[i], &available, &mut issues);
+    }
+
+    issues.sort_by_key(|issue| (issue.claim, issue.offset));
+    LintReport { patent_id, claims_checked: claims.len(), issues }
+}
+
+fn claim_parents(claim: &Claim, index_of: &impl Fn(u32) -> Option<usize>) -> Vec<usize> {
+    claim.depends_on.iter().filter_map(|&n| index_of(n)).collect()
+}
+
+/// Report references to missing or later claims; true when there are none.
+fn check_references(
+    claim: &Claim,
+    own: u32,
+    numbers: &[u32],
+    issues: &mut Vec<ClaimIssue>,
+) -> bool {
+    let mut resolved = true;
+    let mut reported = BTreeSet::new();
+    for reference in claims::claim_references(&claim.text) {
+        let n = reference.number;
+        if !reported.insert(n) {
+            continue;
+        }
+        let (kind, message) = if n == own {
+            (IssueKind::LaterClaim, format!("Refers to itself (claim {})", n))
+        } else if n > own {
+            (IssueKind::LaterClaim, format!("Refers to later claim {}", n))
+        } else if !numbers.contains(&n) {
+            (IssueKind::MissingClaim, format!("Refers to claim {}, which does not exist", n))
+        } else {
+            continue;
+        };
+        issues.push(ClaimIssue { claim: own, kind, offset: reference.offset, message });
+        resolved = false;
+    }
+    resolved
+}
+
+/// Split a claim text into words with their character offsets.
+fn words(text: &str) -> Vec<Word<'_>> {
+    let mut words: Vec<Word<'_>> = Vec::new();
+    let mut start: Option<(usize, usize)> = None;
+    for (offset, (pos, c)) in text.char_indices().enumerate() {
+        let in_word = c.is_alphanumeric() || (c == '-' && start.is_some());
+        match (in_word, start) {
+            (true, None) => start = Some((offset, pos)),
+            (false, Some((word_offset, word_pos))) => {
+                words.push(Word {
+                    text: &text[word_pos..pos],
+                    offset: word_offset,
+                    ends_phrase: !c.is_whitespace(),
+                });
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some((word_offset, word_pos)) = start {
+        words.push(Word { text: &text[word_pos..], offset: word_offset, ends_phrase: true });
+    }
+    words
+}
+
+/// Whether `words` at `i` start with the lowercase `pattern`.
+fn starts_with(words: &[Word<'_>], i: usize, pattern: &[&str]) -> bool {
+    pattern.iter().enumerate().all(|(k, p)| {
+        words.get(i + k).is_some_and(|w| w.text.eq_ignore_ascii_case(p))
+            && (k + 1 == pattern.len() || !words[i + k].ends_phrase)
+    })
+}
+
+/// The term starting at word `i`, as comparable (lowercase, singular) words.
+fn term(words: &[Word<'_>], i: usize) -> Vec<String> {
+    let mut term = Vec::new();
+    for word in words.iter().skip(i).take(MAX_TERM_WORDS) {
+        let lower = word.text.to_lowercase();
+        if STOP_WORDS.split(' ').any(|w| w == lower) || lower.chars().all(|c| c.is_ascii_digit()) {
+            break;
+        }
+        term.push(singular(&lower));
+        if word.ends_phrase {
+            break;
+        }
+    }
+    term
+}
+
+fn singular(word: &str) -> String {
+    match word.strip_suffix('s') {
+        Some(stem) if stem.len() > 2 && !stem.ends_with('s') => stem.to_string(),
+        _ => word.to_string(),
+    }
+}
+
+/// Terms introduced by an indefinite article, with the offset of the article.
+fn introduced_terms(words: &[Word<'_>]) -> Vec<(usize, Vec<String>)> {
+    let mut terms = Vec::new();
+    for i in 0..words.len() {
+        let Some(pattern) = INDEFINITE.iter().find(|p| starts_with(words, i, p)) else {
+            continue;
+        };
+        if words[i + pattern.len() - 1].ends_phrase {
+            continue;
+        }
+        let term = term(words, i + pattern.len());
+        if !term.is_empty() {
+            terms.push((words[i].offset, term));
+        }
+    }
+    terms
+}
+
+/// One term is the other, or starts it ("the data" and "a data set")
+fn same_term(a: &[String], b: &[String]) -> bool {
+    let n = a.len().min(b.len());
+    n > 0 && a[..n] == b[..n]
+}
+
+fn check_antecedents(
+    own: u32,
+    words: &[Word<'_>],
+    introduced: &[(usize, Vec<String>)],
+    available: &[&Vec<String>],
+    issues: &mut Vec<ClaimIssue>,
+) {
+    let mut reported: Vec<Vec<String>> = Vec::new();
+    for i in 0..words.len() {
+        if !DEFINITE.iter().any(|d| words[i].text.eq_ignore_ascii_case(d)) || words[i].ends_phrase {
+            continue;
+        }
+        let start = QUANTIFIERS
+            .iter()
+            .find(|q| starts_with(words, i + 1, q))
+            .map_or(i + 1, |q| i + 1 + q.len());
+        let term = term(words, start);
+        let Some(first) = term.first() else { continue };
+        if EXEMPT.contains(&first.as_str()) {
+            continue;
+        }
+
+        let offset = words[i].offset;
+        let defined = introduced.iter().any(|(at, t)| *at < offset && same_term(t, &term))
+            || available.iter().any(|t| same_term(t, &term));
+        if defined || reported.contains(&term) {
+            continue;
+        }
+        let written: Vec<&str> = words[i..start + term.len()].iter().map(|w| w.text).collect();
+        issues.push(ClaimIssue {
+            claim: own,
+            kind: IssueKind::AntecedentBasis,
+            offset,
+            message: format!("No antecedent basis for \"{}\"", written.join(" ")),
+        });
+        reported.push(term);
+    }
+}
+
+fn check_means_plus_function(own: u32, text: &str, issues: &mut Vec<ClaimIssue>) {
+    let lower = normalize(text);
+    let mut triggers: Vec<String> = MEANS_PLUS_FUNCTION.iter().map(|t| t.to_string()).collect();
+    for nonce in NONCE_WORDS {
+        triggers.extend(FUNCTION_WORDS.iter().map(|f| format!("{} {}", nonce, f)));
+    }
+
+    for trigger in &triggers {
+        for (pos, _) in lower.match_indices(trigger.as_str()) {
+            if !is_word(&lower, pos, trigger) {
+                continue;
+            }
+            issues.push(ClaimIssue {
+                claim: own,
+                kind: IssueKind::MeansPlusFunction,
+                offset: lower[..pos].chars().count(),
+                message: format!("\"{}\" may invoke 35 U.S.C. 112(f)", trigger),
+            });
+        }
+    }
+}
+
+/// Read claims from JSON: a patent (as printed by `fetch`), a list of claims, or a list
+/// of claim texts. Claims without a number are numbered in order.
+pub fn claims_from_json(value: &Value) -> Result<Vec<Claim>> {
+    let list = match value {
+        Value::Array(list) => list,
+        Value::Object(patent) => patent
+            .get("claims")
+            .and_then(Value::as_array)
+            .ok_or_else(|| Error::Other("No claims found in the JSON object".to_string()))?,
+        _ => return Err(Error::Other("Expected a patent or a list of claims".to_string())),
+    };
+
+    let raw = list
+        .iter()
+        .enumerate()
+        .map(|(i, item)| {
+            let text = item
+                .as_str()
+                .or_else(|| item["text"].as_str())
+                .ok_or_else(|| Error::Other(format!("Claim {} has no text", i + 1)))?;
+            let number = match &item["number"] {
+                Value::String(s) => s.clone(),
+                Value::Number(n) => n.to_string(),
+                _ => (i + 1).to_string(),
+            };
+            let id = item["id"].as_str().unwrap_or_default().to_string();
+            Ok((number, id, text.to_string()))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(claims::parse_claims(raw))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn lint(texts: &[&str]) -> Vec<ClaimIssue> {
+        let claims = claims_from_json(&json!(texts)).unwrap();
+        lint_claims(None, &claims).issues
+    }
+
+    fn kinds(issues: &[ClaimIssue]) -> Vec<(u32, IssueKind)> {
+        issues.iter().map(|i| (i.claim, i.kind)).collect()
+    }
+
+    #[test]
+    fn test_antecedent_basis() {
+        let issues = lint(&[
+            "1. A method comprising: receiving a query from a user; sampling a data set; and executing the query against the data set and the database.",
+            "2. The method of claim 1, wherein the user and the one or more servers are remote.",
+            "3. A system comprising one or more processors, wherein the processors are coupled to the same memory.",
+        ]);
+        assert_eq!(
+            kinds(&issues),
+            [(1, IssueKind::AntecedentBasis), (2, IssueKind::AntecedentBasis)]
+        );
+        assert_eq!(issues[0].message, "No antecedent basis for \"the database\"");
+        let text = "A method comprising: receiving a query from a user; sampling a data set; and executing the query against the data set and the database.";
+        let offset: usize = text.find("the database").unwrap();
+        assert_eq!(issues[0].offset, offset);
+        // Quantifiers are skipped, and "the user" is introduced by claim 1
+        assert_eq!(issues[1].message, "No antecedent basis for \"the one or more servers\"");
+    }
+
+    #[test]
+    fn test_claim_references() {
+        let issues = lint(&[
+            "1. A device comprising a lens.",
+            "2. The device of claim 1 or 3, wherein the lens is curved.",
+            "3. The device of claim 7, wherein the lens is flat.",
+            "4. The device of any one of claims 1 to 2, wherein the lens is glass.",
+            "5. The device of claim 4 or 1, wherein the lens is coated.",
+        ]);
+        // Claim 3 refers to a missing claim: its "the lens" is not reported as well
+        assert_eq!(
+            kinds(&issues),
+            [
+                (2, IssueKind::LaterClaim),
+                (3, IssueKind::LaterClaim),
+                (4, IssueKind::MultipleDependent),
+                (5, IssueKind::MultipleDependent),
+            ]
+        );
+        assert_eq!(issues[0].message, "Refers to later claim 3");
+        assert_eq!(issues[0].offset, "The device of ".len());
+        assert!(
+            issues[3].message.contains("depends on claim 4, itself a multiple dependent claim")
+        );
+
+        // Gaps in the numbering are missing claims
+        let claims = claims_from_json(&json!([
+            {"number": "00001", "text": "1. A device comprising a lens."},
+            {"number": "00003", "text": "3. The device of claim 2, wherein the lens is flat."}
+        ]))
+        .unwrap();
+        let report = lint_claims(Some("US1A".to_string()), &claims);
+        assert_eq!(report.claims_checked, 2);
+        assert_eq!(kinds(&report.issues), [(3, IssueKind::MissingClaim)]);
+        assert_eq!(report.issues[0].message, "Refers to claim 2, which does not exist");
+    }
+
+    #[test]
+    fn test_means_plus_function() {
+        let issues = lint(&[
+            "1. An apparatus comprising: means for receiving a signal; and a processing module configured to decode the signal.",
+        ]);
+        assert_eq!(
+            kinds(&issues),
+            [(1, IssueKind::MeansPlusFunction), (1, IssueKind::MeansPlusFunction)]
+        );
+        assert_eq!(issues[0].offset, "An apparatus comprising: ".len());
+        assert_eq!(issues[1].message, "\"module configured to\" may invoke 35 U.S.C. 112(f)");
+    }
+
+    #[test]
+    fn test_claims_from_json() {
+        let patent = json!({"id": "US1A", "claims": [{"number": "00001", "id": "CLM-00001", "text": "1. A lens."}]});
+        let claims = claims_from_json(&patent).unwrap();
+        assert_eq!(claims[0].text, "A lens.");
+        assert_eq!(claims[0].id, "CLM-00001");
+
+        let claims = claims_from_json(&json!([{"number": 2, "text": "A lens."}])).unwrap();
+        assert_eq!(claims[0].number, "2");
+
+        assert!(claims_from_json(&json!({"id": "US1A"})).is_err());
+        assert!(claims_from_json(&json!([{"number": 1}])).is_err());
+        assert!(claims_from_json(&json!("A lens.")).is_err());
+    }
+}
@@ -1,7 +1,24 @@
+pub mod batch;
+pub mod cache;
+pub mod claims;
 pub mod config;
+pub mod crawl;
+pub mod dates;
 pub mod error;
+pub mod extract;
+pub mod family;
+pub mod harvest;
+pub mod http_search;
+pub mod images;
+pub mod job;
+pub mod lint;
 pub mod models;
 pub mod patent_search;
+pub mod pdf;
+pub mod remote_browser;
+pub mod retry;
+#[cfg(test)]
+pub(crate) mod test_support;
 
 pub use error::{Error, Result};
 
@@ -1,3 +1,5 @@
+use crate::core::dates::{self, check_range};
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 use url::Url;
 
@@ -8,11 +10,47 @@ pub struct DescriptionParagraph {
     pub text: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+#[derive(Debug, Default, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct Claim {
     pub number: String,
     pub id: String,
+    /// Claim text without the leading claim number
     pub text: String,
+    /// Refers to no other claim
+    #[serde(default)]
+    pub independent: bool,
+    /// Numbers of the claims this claim refers to
+    #[serde(default)]
+    pub depends_on: Vec<u32>,
+    #[serde(default)]
+    pub category: ClaimCategory,
+    /// Outgoing `DEPENDS_ON` edges, one per referenced claim present in the patent
+    #[serde(rename = "DEPENDS_ON", default, skip_serializing_if = "Vec::is_empty")]
+    pub dependencies: Vec<ClaimRef>,
+}
+
+/// What a claim is directed to
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum ClaimCategory {
+    Method,
+    Apparatus,
+    System,
+    /// Computer-readable medium or program product
+    Crm,
+    #[default]
+    Other,
+}
+
+/// A claim referred to by another claim
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ClaimRef {
+    /// `number` of the referenced claim
+    pub number: String,
+    /// `id` of the referenced claim
+    pub id: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
@@ -30,7 +68,11 @@ pub struct SummaryItem {
 
 #[derive(Debug, Serialize, Deserialize, Default, schemars::JsonSchema)]
 pub struct SearchResult {
+    /// Result count as displayed by Google Patents (e.g., "About 12,345 results")
     pub total_results: String,
+    /// `total_results` parsed into a number, when it contains one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_count: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub top_assignees: Option<Vec<SummaryItem>>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -51,14 +93,51 @@ pub struct Patent {
     pub claims: Option<Vec<Claim>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub images: Option<Vec<PatentImage>>,
+    /// Official publication PDF
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pdf_url: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub snippet: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub filing_date: Option<String>,
+    #[serde(
+        default,
+        deserialize_with = "dates::deserialize_lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub filing_date: Option<NaiveDate>,
+    #[serde(
+        default,
+        deserialize_with = "dates::deserialize_lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub priority_date: Option<NaiveDate>,
+    #[serde(
+        default,
+        deserialize_with = "dates::deserialize_lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub publication_date: Option<NaiveDate>,
+    #[serde(
+        default,
+        deserialize_with = "dates::deserialize_lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub grant_date: Option<NaiveDate>,
+    /// Anticipated (or adjusted) expiration date
+    #[serde(
+        default,
+        deserialize_with = "dates::deserialize_lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub expiration_date: Option<NaiveDate>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub assignee: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub inventors: Vec<String>,
+    /// Kind code of the publication (e.g., "B2" for US9152718B2)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind_code: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub related_application: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -67,38 +146,301 @@ pub struct Patent {
     pub family_applications: Option<Vec<ApplicationInfo>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub legal_status: Option<String>,
+    /// CPC (and other) classification codes, one entry per leaf code
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub classifications: Option<Vec<Classification>>,
+    /// Backward citations ("Patent Citations" and "Family Cites Families")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub citations: Option<Vec<Citation>>,
+    /// Forward citations ("Cited By" and "Families Citing this family")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cited_by: Option<Vec<Citation>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub non_patent_citations: Option<Vec<NonPatentCitation>>,
     pub url: String,
 }
 
+impl Patent {
+    /// Whether extraction found neither a title nor claims, e.g. on a page that did not render
+    pub fn is_empty(&self) -> bool {
+        (self.title.is_empty() || self.title == "No Title") && self.claims.is_none()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct Classification {
+    pub code: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// First (inventive) classification of the publication
+    #[serde(default)]
+    pub first: bool,
+    /// Codes from the section down to this code (e.g., ["G", "G06", "G06F", "G06F16/00"])
+    #[serde(default)]
+    pub path: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct Citation {
+    pub publication_number: String,
+    #[serde(
+        default,
+        deserialize_with = "dates::deserialize_lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub priority_date: Option<NaiveDate>,
+    #[serde(
+        default,
+        deserialize_with = "dates::deserialize_lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub publication_date: Option<NaiveDate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assignee: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Cited by the examiner (marked with `*`)
+    #[serde(default)]
+    pub examiner_cited: bool,
+    /// Family-to-family citation rather than a citation of this publication
+    #[serde(default)]
+    pub family: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct NonPatentCitation {
+    pub title: String,
+    /// Cited by the examiner (marked with `*`)
+    #[serde(default)]
+    pub examiner_cited: bool,
+}
+
 #[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct ApplicationInfo {
     pub application_number: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub country_code: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub priority_date: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub filing_date: Option<String>,
+    /// Publication representing the application (e.g., "US9152718B2")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub publication_number: Option<String>,
+    #[serde(
+        default,
+        deserialize_with = "dates::deserialize_lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub priority_date: Option<NaiveDate>,
+    #[serde(
+        default,
+        deserialize_with = "dates::deserialize_lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub filing_date: Option<NaiveDate>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub title: Option<String>,
 }
 
-#[derive(Debug, Default)]
+/// Kind code at the end of a publication number, e.g. "B2" in "US9152718B2".
+pub fn parse_kind_code(patent_id: &str) -> Option<String> {
+    let digits = patent_id.len() - patent_id.trim_end_matches(|c: char| c.is_ascii_digit()).len();
+    let kind_start = patent_id.len().checked_sub(digits + 1)?;
+    let kind = patent_id.get(kind_start..)?;
+    let letter = kind.chars().next()?;
+    let preceded_by_digit =
+        patent_id[..kind_start].chars().last().is_some_and(|c| c.is_ascii_digit());
+    (letter.is_ascii_uppercase() && preceded_by_digit && digits <= 1).then(|| kind.to_string())
+}
+
+/// Parse the number out of a displayed result count, whatever the locale:
+/// "About 12,345 results", "約 12,345 件", "Environ 12 345 résultats", "12.345 Ergebnisse".
+pub fn parse_result_count(text: &str) -> Option<u64> {
+    let start = text.find(|c: char| c.is_ascii_digit())?;
+    let digits: String = text[start..]
+        .chars()
+        .take_while(|c| {
+            c.is_ascii_digit() || matches!(c, ',' | '.' | '\'' | ' ' | '\u{a0}' | '\u{202f}')
+        })
+        .filter(char::is_ascii_digit)
+        .collect();
+    digits.parse().ok()
+}
+
+/// Document language implied by a country code, for the `language` URL parameter
+fn country_language(country: &str) -> Option<&'static str> {
+    match country {
+        "JP" => Some("JAPANESE"),
+        "CN" => Some("CHINESE"),
+        _ => None,
+    }
+}
+
+/// Page sizes accepted by the `num` URL parameter
+const PAGE_SIZES: [usize; 4] = [10, 20, 50, 100];
+const DEFAULT_PAGE_SIZE: usize = 10;
+pub const MAX_PAGE_SIZE: usize = 100;
+
+/// Check a `num` value against the page sizes Google Patents serves.
+pub fn check_page_size(num: usize) -> crate::core::Result<usize> {
+    if PAGE_SIZES.contains(&num) {
+        Ok(num)
+    } else {
+        Err(crate::core::Error::Config(format!(
+            "Invalid results per page: {} (expected 10, 20, 50 or 100)",
+            num
+        )))
+    }
+}
+
+/// Publication status filter (`status=`)
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    schemars::JsonSchema,
+    clap::ValueEnum,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum PatentStatus {
+    /// Granted patents only
+    Grant,
+    /// Applications only
+    Application,
+}
+
+impl PatentStatus {
+    pub fn as_param(&self) -> &'static str {
+        match self {
+            Self::Grant => "GRANT",
+            Self::Application => "APPLICATION",
+        }
+    }
+}
+
+/// Patent type filter (`type=`)
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    schemars::JsonSchema,
+    clap::ValueEnum,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum PatentType {
+    /// Utility patents
+    Patent,
+    /// Design patents
+    Design,
+}
+
+impl PatentType {
+    pub fn as_param(&self) -> &'static str {
+        match self {
+            Self::Patent => "PATENT",
+            Self::Design => "DESIGN",
+        }
+    }
+}
+
+/// Result ordering (`sort=`); relevance when unset
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    schemars::JsonSchema,
+    clap::ValueEnum,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    /// Newest first
+    New,
+    /// Oldest first
+    Old,
+}
+
+impl SortOrder {
+    pub fn as_param(&self) -> &'static str {
+        match self {
+            Self::New => "new",
+            Self::Old => "old",
+        }
+    }
+}
+
+/// How results are deduplicated (`dups=`)
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    schemars::JsonSchema,
+    clap::ValueEnum,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Dedup {
+    /// One result per patent family (Google's default)
+    Family,
+    /// One result per publication language
+    Language,
+}
+
+impl Dedup {
+    /// URL value, or `None` for the default that needs no parameter
+    pub fn as_param(&self) -> Option<&'static str> {
+        match self {
+            Self::Family => None,
+            Self::Language => Some("language"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
 pub struct SearchOptions {
     pub query: Option<String>,
     pub assignee: Option<Vec<String>>,
-    pub country: Option<String>,
+    pub inventor: Option<Vec<String>>,
+    /// CPC classification codes (e.g., "G06N20/00")
+    pub cpc: Option<Vec<String>>,
+    /// Country codes (e.g., "JP", "US"), matched as alternatives
+    pub country: Option<Vec<String>>,
     pub patent_number: Option<String>,
     // Priority date filters (earliest filing date)
-    pub priority_after: Option<String>,
-    pub priority_before: Option<String>,
+    pub priority_after: Option<NaiveDate>,
+    pub priority_before: Option<NaiveDate>,
     // Publication date filters
-    pub publication_after: Option<String>,
-    pub publication_before: Option<String>,
+    pub publication_after: Option<NaiveDate>,
+    pub publication_before: Option<NaiveDate>,
     // Filing date filters
-    pub filing_after: Option<String>,
-    pub filing_before: Option<String>,
+    pub filing_after: Option<NaiveDate>,
+    pub filing_before: Option<NaiveDate>,
+    pub status: Option<PatentStatus>,
+    pub patent_type: Option<PatentType>,
+    /// Only patents involved in litigation
+    pub litigation: bool,
+    pub sort: Option<SortOrder>,
+    pub dedup: Option<Dedup>,
     pub limit: Option<usize>,
+    /// Results per page (10, 20, 50 or 100); derived from `limit` when unset
+    pub num: Option<usize>,
+    /// Results page to start from (0-based), in pages of `results_per_page()`
+    pub start_page: usize,
     /// Language/locale for the patent page (e.g., "ja", "en", "zh")
     pub language: Option<String>,
 }
@@ -139,43 +481,72 @@ impl SearchOptions {
                 }
             }
 
-            if let Some(country) = &self.country {
-                serializer.append_pair("country", country);
-                match country.to_uppercase().as_str() {
-                    "JP" => {
-                        serializer.append_pair("language", "JAPANESE");
-                    }
-                    "CN" => {
-                        serializer.append_pair("language", "CHINESE");
-                    }
-                    _ => {}
+            if let Some(inventors) = &self.inventor {
+                for i in inventors {
+                    serializer.append_pair("inventor", i);
+                }
+            }
+
+            if let Some(cpcs) = &self.cpc {
+                for code in cpcs {
+                    serializer.append_pair("cpc", code);
                 }
             }
 
-            // Priority date filters
-            // Convert YYYY-MM-DD to YYYYMMDD format for Google Patents
+            if let Some(countries) = &self.country
+                && !countries.is_empty()
+            {
+                let codes: Vec<String> = countries.iter().map(|c| c.to_uppercase()).collect();
+                serializer.append_pair("country", &codes.join(","));
+                // Restrict the language only when every country shares it, so adding
+                // e.g. US to JP does not filter out the English documents
+                let mut languages = codes.iter().map(|c| country_language(c));
+                if let Some(Some(language)) = languages.next()
+                    && languages.all(|l| l == Some(language))
+                {
+                    serializer.append_pair("language", language);
+                }
+            }
+
+            // Priority date filters (Google Patents expects YYYYMMDD)
             if let Some(after) = &self.priority_after {
-                serializer.append_pair("after", &format!("priority:{}", after.replace("-", "")));
+                serializer.append_pair("after", &format!("priority:{}", after.format("%Y%m%d")));
             }
             if let Some(before) = &self.priority_before {
-                serializer.append_pair("before", &format!("priority:{}", before.replace("-", "")));
+                serializer.append_pair("before", &format!("priority:{}", before.format("%Y%m%d")));
             }
 
             // Publication date filters
             if let Some(after) = &self.publication_after {
-                serializer.append_pair("after", &format!("publication:{}", after.replace("-", "")));
+                serializer.append_pair("after", &format!("publication:{}", after.format("%Y%m%d")));
             }
             if let Some(before) = &self.publication_before {
                 serializer
-                    .append_pair("before", &format!("publication:{}", before.replace("-", "")));
+                    .append_pair("before", &format!("publication:{}", before.format("%Y%m%d")));
             }
 
             // Filing date filters
             if let Some(after) = &self.filing_after {
-                serializer.append_pair("after", &format!("filing:{}", after.replace("-", "")));
+                serializer.append_pair("after", &format!("filing:{}", after.format("%Y%m%d")));
             }
             if let Some(before) = &self.filing_before {
-                serializer.append_pair("before", &format!("filing:{}", before.replace("-", "")));
+                serializer.append_pair("before", &format!("filing:{}", before.format("%Y%m%d")));
+            }
+
+            if let Some(status) = self.status {
+                serializer.append_pair("status", status.as_param());
+            }
+            if let Some(patent_type) = self.patent_type {
+                serializer.append_pair("type", patent_type.as_param());
+            }
+            if self.litigation {
+                serializer.append_pair("litigation", "YES");
+            }
+            if let Some(sort) = self.sort {
+                serializer.append_pair("sort", sort.as_param());
+            }
+            if let Some(dups) = self.dedup.and_then(|d| d.as_param()) {
+                serializer.append_pair("dups", dups);
             }
 
             if let Some(language) = &self.language {
@@ -189,12 +560,46 @@ impl SearchOptions {
         // Check if url string ends with / or /? and has no params
         if !url_str.contains('?') || url_str.ends_with('?') {
             return Err(crate::core::Error::Search(
-                "Must provide either --query, --assignee or --patent".to_string(),
+                "Must provide either --query, --assignee, --inventor, --cpc or --patent"
+                    .to_string(),
             ));
         }
 
         Ok(url_str)
     }
+
+    /// Reject inverted date ranges (after > before).
+    pub fn validate_dates(&self) -> crate::core::Result<()> {
+        check_range("priority", self.priority_after, self.priority_before)?;
+        check_range("publication", self.publication_after, self.publication_before)?;
+        check_range("filing", self.filing_after, self.filing_before)
+    }
+
+    /// Build the URL for a specific results page (0-based).
+    ///
+    /// Google Patents serves at most 100 results per page, so larger limits
+    /// are fetched by walking the `page` parameter.
+    pub fn to_page_url(&self, page: usize) -> crate::core::Result<String> {
+        let mut url = Url::parse(&self.to_url()?)?;
+        let num = self.results_per_page();
+        {
+            let mut serializer = url.query_pairs_mut();
+            if num != DEFAULT_PAGE_SIZE {
+                serializer.append_pair("num", &num.to_string());
+            }
+            let page = self.start_page + page;
+            if page > 0 {
+                serializer.append_pair("page", &page.to_string());
+            }
+        }
+        Ok(url.to_string())
+    }
+
+    /// Smallest page size supported by Google Patents that covers `num`, or the limit.
+    pub fn results_per_page(&self) -> usize {
+        let limit = self.num.or(self.limit).unwrap_or(DEFAULT_PAGE_SIZE);
+        PAGE_SIZES.iter().copied().find(|&n| n >= limit).unwrap_or(MAX_PAGE_SIZE)
+    }
 }
 
 #[cfg(test)]
@@ -215,6 +620,43 @@ mod tests {
         assert_eq!(patent.title, "System and method for interactive big data analysis");
         assert_eq!(patent.url, "https://patents.google.com/patent/US9152718B2");
         assert!(patent.abstract_text.is_none());
+
+        // Dates are typed; placeholders such as "Unknown" are dropped
+        let json = r#"{
+            "id": "US9152718B2",
+            "title": "t",
+            "url": "u",
+            "filing_date": "2013-08-06",
+            "priority_date": "Unknown",
+            "grant_date": null
+        }"#;
+        let patent: Patent = serde_json::from_str(json).unwrap();
+        assert_eq!(patent.filing_date, NaiveDate::from_ymd_opt(2013, 8, 6));
+        assert!(patent.priority_date.is_none());
+        assert!(patent.grant_date.is_none());
+        assert!(serde_json::to_string(&patent).unwrap().contains(r#""filing_date":"2013-08-06""#));
+    }
+
+    #[test]
+    fn test_parse_result_count() {
+        assert_eq!(parse_result_count("About 12,345 results"), Some(12345));
+        assert_eq!(parse_result_count("約 12,345 件"), Some(12345));
+        assert_eq!(parse_result_count("Environ 1\u{202f}234\u{a0}567 résultats"), Some(1234567));
+        assert_eq!(parse_result_count("Ungefähr 12.345 Ergebnisse"), Some(12345));
+        assert_eq!(parse_result_count("1 result"), Some(1));
+        assert_eq!(parse_result_count("1000"), Some(1000));
+        assert_eq!(parse_result_count("Unknown"), None);
+        assert_eq!(parse_result_count(""), None);
+    }
+
+    #[test]
+    fn test_parse_kind_code() {
+        assert_eq!(parse_kind_code("US9152718B2").as_deref(), Some("B2"));
+        assert_eq!(parse_kind_code("JP2019123456A").as_deref(), Some("A"));
+        assert_eq!(parse_kind_code("DE102018215057B4").as_deref(), Some("B4"));
+        assert_eq!(parse_kind_code("Unknown"), None);
+        assert_eq!(parse_kind_code("12345"), None);
+        assert_eq!(parse_kind_code(""), None);
     }
 
     #[test]
@@ -275,10 +717,20 @@ mod tests {
         assert!(url.contains("q=foo"));
         assert!(url.contains("assignee=Google+LLC"));
 
+        // Test CPC filter (slash is kept readable by Google but encoded here)
+        let options = SearchOptions {
+            cpc: Some(vec!["G06N20/00".to_string(), "G06F16/2453".to_string()]),
+            ..Default::default()
+        };
+        assert_eq!(
+            options.to_url().unwrap(),
+            "https://patents.google.com/?cpc=G06N20%2F00&cpc=G06F16%2F2453"
+        );
+
         // Test query with country (JP should add language=JAPANESE)
         let options = SearchOptions {
             query: Some("foo".to_string()),
-            country: Some("JP".to_string()),
+            country: Some(vec!["JP".to_string()]),
             ..Default::default()
         };
         assert_eq!(
@@ -289,7 +741,7 @@ mod tests {
         // Test query with country (CN should add language=CHINESE)
         let options = SearchOptions {
             query: Some("foo".to_string()),
-            country: Some("CN".to_string()),
+            country: Some(vec!["CN".to_string()]),
             ..Default::default()
         };
         assert_eq!(
@@ -300,16 +752,27 @@ mod tests {
         // Test query with country (US should NOT add language)
         let options = SearchOptions {
             query: Some("foo".to_string()),
-            country: Some("US".to_string()),
+            country: Some(vec!["US".to_string()]),
             ..Default::default()
         };
         assert_eq!(options.to_url().unwrap(), "https://patents.google.com/?q=foo&country=US");
 
+        // Multiple countries are comma-joined; mixed languages add no language filter
+        let options = SearchOptions {
+            query: Some("foo".to_string()),
+            country: Some(vec!["jp".to_string(), "US".to_string(), "WO".to_string()]),
+            ..Default::default()
+        };
+        assert_eq!(
+            options.to_url().unwrap(),
+            "https://patents.google.com/?q=foo&country=JP%2CUS%2CWO"
+        );
+
         // Test query with priority dates
         let options = SearchOptions {
             query: Some("foo".to_string()),
-            priority_after: Some("2020-01-01".to_string()),
-            priority_before: Some("2021-01-01".to_string()),
+            priority_after: NaiveDate::from_ymd_opt(2020, 1, 1),
+            priority_before: NaiveDate::from_ymd_opt(2021, 1, 1),
             ..Default::default()
         };
         let url = options.to_url().unwrap();
@@ -344,7 +807,7 @@ mod tests {
         // Test search with country and explicit language override
         let options = SearchOptions {
             query: Some("foo".to_string()),
-            country: Some("JP".to_string()),
+            country: Some(vec!["JP".to_string()]),
             language: Some("en".to_string()),
             ..Default::default()
         };
@@ -353,4 +816,112 @@ mod tests {
         assert!(url.contains("language=JAPANESE"));
         assert!(url.contains("hl=en"));
     }
+
+    #[test]
+    fn test_validate_dates() {
+        let options = SearchOptions {
+            query: Some("foo".to_string()),
+            priority_after: NaiveDate::from_ymd_opt(2020, 1, 1),
+            priority_before: NaiveDate::from_ymd_opt(2021, 1, 1),
+            ..Default::default()
+        };
+        assert!(options.validate_dates().is_ok());
+
+        // Inverted ranges are rejected
+        let options = SearchOptions {
+            query: Some("foo".to_string()),
+            filing_after: NaiveDate::from_ymd_opt(2021, 1, 1),
+            filing_before: NaiveDate::from_ymd_opt(2020, 1, 1),
+            ..Default::default()
+        };
+        assert!(matches!(options.validate_dates(), Err(crate::core::Error::InvalidDate(_))));
+    }
+
+    #[test]
+    fn test_search_options_to_page_url() {
+        // Default limit keeps the first page URL unchanged
+        let options = SearchOptions { query: Some("foo".to_string()), ..Default::default() };
+        assert_eq!(options.to_page_url(0).unwrap(), "https://patents.google.com/?q=foo");
+        assert_eq!(options.to_page_url(2).unwrap(), "https://patents.google.com/?q=foo&page=2");
+
+        // Larger limits pick the smallest page size that covers them
+        let options =
+            SearchOptions { query: Some("foo".to_string()), limit: Some(15), ..Default::default() };
+        assert_eq!(options.results_per_page(), 20);
+        assert_eq!(options.to_page_url(0).unwrap(), "https://patents.google.com/?q=foo&num=20");
+
+        // Limits above 100 are capped and paged
+        let options = SearchOptions {
+            query: Some("foo".to_string()),
+            limit: Some(250),
+            ..Default::default()
+        };
+        assert_eq!(options.results_per_page(), 100);
+        assert_eq!(
+            options.to_page_url(1).unwrap(),
+            "https://patents.google.com/?q=foo&num=100&page=1"
+        );
+
+        // An explicit page size wins over the limit
+        let options = SearchOptions {
+            query: Some("foo".to_string()),
+            limit: Some(5),
+            num: Some(50),
+            ..Default::default()
+        };
+        assert_eq!(options.to_page_url(0).unwrap(), "https://patents.google.com/?q=foo&num=50");
+
+        // Pages are counted from the start page
+        let options = SearchOptions { start_page: 1, ..options };
+        assert_eq!(
+            options.to_page_url(0).unwrap(),
+            "https://patents.google.com/?q=foo&num=50&page=1"
+        );
+
+        // Other page sizes are rejected rather than rounded
+        assert_eq!(check_page_size(20).unwrap(), 20);
+        assert!(check_page_size(7).is_err());
+        assert!(check_page_size(1000).is_err());
+    }
+
+    #[test]
+    fn test_search_options_filters_to_url() {
+        // Inventor alone is enough to search
+        let options = SearchOptions {
+            inventor: Some(vec!["Jane Doe".to_string(), "John Roe".to_string()]),
+            ..Default::default()
+        };
+        assert_eq!(
+            options.to_url().unwrap(),
+            "https://patents.google.com/?inventor=Jane+Doe&inventor=John+Roe"
+        );
+
+        let options = SearchOptions {
+            query: Some("foo".to_string()),
+            status: Some(PatentStatus::Grant),
+            patent_type: Some(PatentType::Design),
+            litigation: true,
+            sort: Some(SortOrder::New),
+            dedup: Some(Dedup::Language),
+            ..Default::default()
+        };
+        assert_eq!(
+            options.to_url().unwrap(),
+            "https://patents.google.com/?q=foo&status=GRANT&type=DESIGN&litigation=YES&sort=new&dups=language"
+        );
+
+        let options = SearchOptions {
+            query: Some("foo".to_string()),
+            status: Some(PatentStatus::Application),
+            patent_type: Some(PatentType::Patent),
+            sort: Some(SortOrder::Old),
+            // Family deduplication is Google's default and needs no parameter
+            dedup: Some(Dedup::Family),
+            ..Default::default()
+        };
+        assert_eq!(
+            options.to_url().unwrap(),
+            "https://patents.google.com/?q=foo&status=APPLICATION&type=PATENT&sort=old"
+        );
+    }
 }
@@ -1,16 +1,75 @@
-use crate::core::models::{Patent, SearchOptions, SearchResult};
+use crate::core::config::{Backend, Config};
+use crate::core::extract::{parse_patent_html, parse_search_results_html};
+use crate::core::http_search::HttpPatentSearcher;
+use crate::core::models::{SearchOptions, SearchResult, SummaryItem};
+use crate::core::remote_browser::RemoteBrowser;
+use crate::core::retry::RetryPolicy;
 use crate::core::{BrowserManager, CdpPage};
 use crate::core::{Error, Result};
 use async_trait::async_trait;
+use std::collections::HashSet;
+use std::sync::Arc;
 
 #[async_trait]
 pub trait PatentSearch: Send + Sync {
     async fn search(&self, options: &SearchOptions) -> Result<SearchResult>;
+
+    /// Full HTML of a patent page (after rendering, where applicable), for debugging extraction
+    async fn fetch_raw_html(&self, _options: &SearchOptions) -> Result<String> {
+        Err(Error::Search("Raw HTML is not supported by this backend".to_string()))
+    }
+
+    /// Release resources before exiting, e.g. tabs opened in an attached browser
+    async fn shutdown(&self) {}
+}
+
+/// Create a searcher for the selected backend.
+pub async fn create_searcher(
+    config: &Config,
+    backend: Backend,
+    headless: bool,
+    debug: bool,
+    verbose: bool,
+) -> Result<Arc<dyn PatentSearch>> {
+    match backend {
+        Backend::Browser => {
+            let (browser_path, chrome_args) = config.resolve();
+            let searcher = PatentSearcher::new(
+                browser_path,
+                headless,
+                debug,
+                verbose,
+                chrome_args,
+                config.debug_ws_url.as_deref(),
+            )
+            .await?
+            .with_retry_policy(config.retry.clone());
+            Ok(Arc::new(searcher))
+        }
+        Backend::Http => {
+            let searcher = HttpPatentSearcher::new(config.base_url(), verbose)?
+                .with_retry_policy(config.retry.clone());
+            Ok(Arc::new(searcher))
+        }
+    }
+}
+
+/// Message returned when Google serves its bot-detection ("Sorry...") page
+pub(crate) const BOT_DETECTED_MESSAGE: &str = "Google blocked this request (bot detection / rate limiting). \
+     The IP address may be temporarily blocked. Try again later.";
+
+/// Browser the searcher opens its tabs in
+enum BrowserHandle {
+    /// Chrome launched (and shut down) by us
+    Launched(BrowserManager),
+    /// Already running Chrome we attached to over CDP
+    Attached(RemoteBrowser),
 }
 
 pub struct PatentSearcher {
-    browser_manager: BrowserManager,
+    browser: BrowserHandle,
     verbose: bool,
+    retry_policy: RetryPolicy,
 }
 
 #[async_trait]
@@ -19,6 +78,23 @@ impl PatentSearch for PatentSearcher {
     async fn search(&self, options: &SearchOptions) -> Result<SearchResult> {
         self.search_internal(options).await
     }
+
+    /// Fetch the rendered patent page as `outerHTML`
+    async fn fetch_raw_html(&self, options: &SearchOptions) -> Result<String> {
+        let url = options.to_url()?;
+        let page = self.open_page().await?;
+
+        let html = self.read_page_html(&page, &url).await;
+        let _ = page.close().await;
+        html?.ok_or_else(|| Error::Search(format!("No patent page at {}", url)))
+    }
+
+    /// Detach from an attached browser without shutting it down
+    async fn shutdown(&self) {
+        if let BrowserHandle::Attached(remote) = &self.browser {
+            remote.detach().await;
+        }
+    }
 }
 
 impl PatentSearcher {
@@ -28,127 +104,204 @@ impl PatentSearcher {
         debug: bool,
         verbose: bool,
         chrome_args: Vec<String>,
+        debug_ws_url: Option<&str>,
     ) -> Result<Self> {
-        let browser_manager = BrowserManager::new(browser_path, headless, debug, chrome_args);
+        // Attach to a running browser when a DevTools URL is given, otherwise launch one
+        let browser = match debug_ws_url {
+            Some(ws_url) => {
+                if verbose {
+                    eprintln!("Attaching to browser at {}", ws_url);
+                }
+                BrowserHandle::Attached(RemoteBrowser::connect(ws_url).await?)
+            }
+            None => BrowserHandle::Launched(BrowserManager::new(
+                browser_path,
+                headless,
+                debug,
+                chrome_args,
+            )),
+        };
+
+        Ok(Self { browser, verbose, retry_policy: RetryPolicy::default() })
+    }
 
-        Ok(Self { browser_manager, verbose })
+    /// Open a new tab in the browser
+    async fn open_page(&self) -> Result<CdpPage> {
+        let page_ws_url = match &self.browser {
+            BrowserHandle::Launched(manager) => manager.get_browser().await?.new_page().await?,
+            BrowserHandle::Attached(remote) => remote.new_page().await?,
+        };
+        Ok(CdpPage::new(&page_ws_url, std::time::Duration::from_secs(30)).await?)
     }
 
-    async fn search_internal(&self, options: &SearchOptions) -> Result<SearchResult> {
-        let browser = self.browser_manager.get_browser().await?;
-        let page_ws_url = browser.new_page().await?;
-        let page = CdpPage::new(&page_ws_url, std::time::Duration::from_secs(30)).await?;
+    /// Set the retry policy used around page navigation
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Rendered HTML of a patent page, or `None` if Google has no such patent
+    async fn read_page_html(&self, page: &CdpPage, url: &str) -> Result<Option<String>> {
+        if !self.load_patent_page(page, url).await? {
+            return Ok(None);
+        }
+        outer_html(page).await.map(Some)
+    }
+
+    /// Navigate to a patent page and wait until its content has rendered.
+    ///
+    /// Returns `false` when Google answers with its "not found" page.
+    async fn load_patent_page(&self, page: &CdpPage, url: &str) -> Result<bool> {
+        self.retry_policy.retry(self.verbose, || self.navigate(page, url)).await?;
+        if page_title(page).await.starts_with("Error 404") {
+            return Ok(false);
+        }
+
+        if self.verbose {
+            eprintln!("Waiting for page to load...");
+        }
+        // Wait for meta description or title tag to ensure page is loaded
+        let loaded =
+            page.wait_for_element("meta[name='description'], meta[name='DC.title']", 15).await?;
+        if !loaded {
+            return Err(Error::Search("Page failed to load within timeout".to_string()));
+        }
+        // Additional wait for description paragraphs, claims, and images to appear
+        let _ = page
+            .wait_for_element(
+                "div.description-paragraph[num], div.claim[num], img[src*='patentimages']",
+                15,
+            )
+            .await?;
+
+        // Give a little time for all dynamic content (like claims) to fully render
+        tokio::time::sleep(std::time::Duration::from_millis(1000)).await;
+        Ok(true)
+    }
+
+    /// Navigate to a URL and check for the bot detection / rate limiting page
+    async fn navigate(&self, page: &CdpPage, url: &str) -> Result<()> {
+        page.goto(url).await?;
+
+        if page_title(page).await == "Sorry..." {
+            return Err(Error::RateLimited(BOT_DETECTED_MESSAGE.to_string()));
+        }
+
+        Ok(())
+    }
 
+    async fn search_internal(&self, options: &SearchOptions) -> Result<SearchResult> {
         let base_url = options.to_url()?;
 
         if self.verbose {
             eprintln!("Search URL: {}", base_url);
         }
 
+        let page = self.open_page().await?;
+
         if let Some(patent_number) = &options.patent_number {
             // Single patent lookup - no pagination needed
             if self.verbose {
                 eprintln!("Fetching single patent: {}", patent_number);
             }
-            page.goto(&base_url).await?;
-
-            // Check for bot detection / rate limiting page
-            let title = page
-                .evaluate("document.title")
-                .await
-                .ok()
-                .and_then(|v| v.as_str().map(String::from))
-                .unwrap_or_default();
-            if title == "Sorry..." {
-                let _ = page.close().await;
-                return Err(Error::Search(
-                    "Google blocked this request (bot detection / rate limiting). \
-                     The IP address may be temporarily blocked. Try again later."
-                        .to_string(),
-                ));
-            }
-
-            if self.verbose {
-                eprintln!("Waiting for page to load...");
-            }
-            // Wait for meta description or title tag to ensure page is loaded
-            let loaded = page
-                .wait_for_element("meta[name='description'], meta[name='DC.title']", 15)
-                .await?;
-            if !loaded {
-                return Err(Error::Search("Page failed to load within timeout".to_string()));
-            }
-            // Additional wait for description paragraphs, claims, and images to appear
-            let _ = page
-                .wait_for_element(
-                    "div.description-paragraph[num], div.claim[num], img[src*='patentimages']",
-                    15,
-                )
-                .await?;
-
-            // Give a little time for all dynamic content (like claims) to fully render
-            tokio::time::sleep(std::time::Duration::from_millis(1000)).await;
+            let html = self.read_page_html(&page, &base_url).await;
+            let _ = page.close().await;
+            let Some(html) = html? else {
+                return Ok(SearchResult {
+                    total_results: "0".to_string(),
+                    total_count: Some(0),
+                    ..Default::default()
+                });
+            };
 
             if self.verbose {
                 eprintln!("Extracting patent data...");
             }
-            // Single patent page - extract structured data
-            let result = page.evaluate(include_str!("scripts/extract_patent.js")).await?;
-
-            // For single patent, total_results is "1".
-            let patents = parse_single_patent_result(result, patent_number, base_url)?;
-            let _ = page.close().await;
+            // The rendered page is parsed like a server-rendered one (HTTP backend)
+            let patents = vec![parse_patent_html(&html, patent_number, &base_url)];
 
             Ok(SearchResult {
                 total_results: "1".to_string(),
+                total_count: Some(1),
                 top_assignees: None,
                 top_cpcs: None,
                 patents,
             })
         } else {
-            // Search results page - scrape from DOM
+            // Search results pages - scrape from DOM, walking pages until limit is reached
             let limit = options.limit.unwrap_or(10);
+            let per_page = options.results_per_page();
 
             if self.verbose {
                 eprintln!("Fetching search results (limit: {})...", limit);
             }
 
-            page.goto(&base_url).await?;
-
-            // Check for bot detection / rate limiting page
-            let title = page
-                .evaluate("document.title")
-                .await
-                .ok()
-                .and_then(|v| v.as_str().map(String::from))
-                .unwrap_or_default();
-            if title == "Sorry..." {
-                let _ = page.close().await;
-                return Err(Error::Search(
-                    "Google blocked this request (bot detection / rate limiting). \
-                     The IP address may be temporarily blocked. Try again later."
-                        .to_string(),
-                ));
-            }
-
-            if self.verbose {
-                eprintln!("Waiting for search results to load...");
-            }
-            // Wait for search results to render
-            tokio::time::sleep(std::time::Duration::from_secs(3)).await;
-
-            if self.verbose {
-                eprintln!("Extracting search results from DOM...");
+            // Close the tab however collecting ends, so failures do not leak tabs
+            let collected = async {
+                let mut sr = SearchResult::default();
+                let mut seen = HashSet::new();
+                let mut page_index = 0;
+
+                while sr.patents.len() < limit {
+                    let page_url = options.to_page_url(page_index)?;
+                    if self.verbose && page_index > 0 {
+                        eprintln!("Fetching results page {}: {}", page_index + 1, page_url);
+                    }
+
+                    self.retry_policy
+                        .retry(self.verbose, || self.navigate(&page, &page_url))
+                        .await?;
+
+                    if self.verbose {
+                        eprintln!("Waiting for search results to load...");
+                    }
+                    // Wait for search results to render
+                    tokio::time::sleep(std::time::Duration::from_secs(3)).await;
+                    let _ = page.wait_for_element("search-result-item", 3).await;
+
+                    // The summary box is only needed once; it summarizes the whole query
+                    if page_index == 0 {
+                        let _ = page.evaluate(include_str!("scripts/click_expand.js")).await;
+                        tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+                    }
+
+                    if self.verbose {
+                        eprintln!("Extracting search results from DOM...");
+                    }
+                    // The rendered page is parsed like a saved one (`search --from-html`)
+                    let mut page_result = parse_search_results_html(&outer_html(&page).await?);
+
+                    if page_index == 0 {
+                        page_result.top_cpcs = self.extract_top_cpcs(&page).await;
+                    }
+
+                    let page_len = page_result.patents.len();
+                    let added = merge_search_page(&mut sr, page_result, &mut seen, page_index == 0);
+
+                    if self.verbose {
+                        eprintln!(
+                            "Patents on page {}: {} ({} new)",
+                            page_index + 1,
+                            page_len,
+                            added
+                        );
+                    }
+
+                    // A short or duplicate-only page means there is nothing more to fetch
+                    if page_len < per_page || added == 0 {
+                        break;
+                    }
+                    page_index += 1;
+                }
+                Ok::<_, Error>(sr)
             }
-            let result = page.evaluate(include_str!("scripts/extract_search_results.js")).await?;
-            let mut sr: SearchResult = serde_json::from_value(result)
-                .map_err(|e| Error::Search(format!("Failed to parse search results: {}", e)))?;
-
+            .await;
             let _ = page.close().await;
+            let mut sr = collected?;
 
             if self.verbose {
                 eprintln!("Total results found: {}", sr.total_results);
-                eprintln!("Patents on page: {}", sr.patents.len());
+                eprintln!("Patents collected: {}", sr.patents.len());
             }
 
             if sr.patents.len() > limit {
@@ -158,92 +311,81 @@ impl PatentSearcher {
             Ok(sr)
         }
     }
+
+    /// Extract the CPC facet from a search results page.
+    ///
+    /// The CPC breakdown is hidden behind a tab in the summary box, so this is a
+    /// two-step process: click the "CPCs" tab (and "Expand"), then read the
+    /// `data-cpc` items once they have rendered. Failures are not fatal.
+    async fn extract_top_cpcs(&self, page: &CdpPage) -> Option<Vec<SummaryItem>> {
+        if self.verbose {
+            eprintln!("Extracting CPC facet...");
+        }
+
+        let clicked = page.evaluate(include_str!("scripts/click_cpcs_tab.js")).await.ok()?;
+        if !clicked["clicked"].as_bool().unwrap_or(false) {
+            if self.verbose {
+                eprintln!("CPCs tab not found; skipping CPC facet");
+            }
+            return None;
+        }
+
+        // Wait for the CPC items to replace the assignee items in the summary box
+        let _ = page.wait_for_element("state-modifier[data-cpc]", 5).await;
+
+        let html = outer_html(page).await.ok()?;
+        parse_search_results_html(&html).top_cpcs
+    }
 }
 
-fn parse_single_patent_result(
-    result: serde_json::Value,
-    patent_number: &str,
-    url: String,
-) -> Result<Vec<Patent>> {
-    let title = result["title"].as_str().unwrap_or("No Title").to_string();
-    let abstract_text = result["abstract"].as_str().map(String::from);
-
-    // Parse description paragraphs
-    let description_paragraphs = result["description_paragraphs"].as_array().and_then(|paras| {
-        let parsed: Vec<crate::core::models::DescriptionParagraph> = paras
-            .iter()
-            .filter_map(|p| {
-                Some(crate::core::models::DescriptionParagraph {
-                    number: p["number"].as_str()?.to_string(),
-                    id: p["id"].as_str()?.to_string(),
-                    text: p["text"].as_str()?.to_string(),
-                })
-            })
-            .collect();
-        if parsed.is_empty() { None } else { Some(parsed) }
-    });
-
-    // Parse claims
-    let claims = result["claims"].as_array().and_then(|claims_arr| {
-        let parsed: Vec<crate::core::models::Claim> = claims_arr
-            .iter()
-            .filter_map(|c| {
-                Some(crate::core::models::Claim {
-                    number: c["number"].as_str()?.to_string(),
-                    id: c["id"].as_str()?.to_string(),
-                    text: c["text"].as_str()?.to_string(),
-                })
-            })
-            .collect();
-        if parsed.is_empty() { None } else { Some(parsed) }
-    });
-
-    // Parse images
-    let images = result["images"].as_array().and_then(|imgs| {
-        let parsed: Vec<crate::core::models::PatentImage> = imgs
-            .iter()
-            .filter_map(|img| {
-                Some(crate::core::models::PatentImage {
-                    url: img["url"].as_str()?.to_string(),
-                    figure_number: img["figure_number"].as_str().map(String::from),
-                })
-            })
-            .collect();
-        if parsed.is_empty() { None } else { Some(parsed) }
-    });
-
-    let filing_date = result["filing_date"].as_str().map(String::from);
-    let assignee = result["assignee"].as_str().map(String::from);
-    let related_application: Option<String> =
-        result["related_application"].as_str().map(String::from);
-    let claiming_priority: Option<Vec<crate::core::models::ApplicationInfo>> =
-        serde_json::from_value(result["claiming_priority"].clone()).unwrap_or(None);
-    let family_applications: Option<Vec<crate::core::models::ApplicationInfo>> =
-        serde_json::from_value(result["family_applications"].clone()).unwrap_or(None);
-    let legal_status = result["legal_status"].as_str().map(String::from);
-
-    Ok(vec![Patent {
-        id: patent_number.to_string(),
-        title,
-        abstract_text,
-        description_paragraphs,
-        claims,
-        images,
-        snippet: None,
-        description: None,
-        filing_date,
-        assignee,
-        related_application,
-        claiming_priority,
-        family_applications,
-        legal_status,
-        url,
-    }])
+/// Title of the page, or an empty string if it cannot be read.
+async fn page_title(page: &CdpPage) -> String {
+    page.evaluate("document.title")
+        .await
+        .ok()
+        .and_then(|v| v.as_str().map(String::from))
+        .unwrap_or_default()
+}
+
+/// Rendered HTML of the whole page.
+async fn outer_html(page: &CdpPage) -> Result<String> {
+    let html = page.evaluate("document.documentElement.outerHTML").await?;
+    html.as_str()
+        .map(String::from)
+        .ok_or_else(|| Error::Search("Failed to read page HTML".to_string()))
+}
+
+/// Merge one results page into the accumulated result, skipping patents already seen.
+///
+/// Summary fields (total count, top assignees/CPCs) are taken from the first page only.
+/// Returns the number of newly added patents.
+pub(crate) fn merge_search_page(
+    acc: &mut SearchResult,
+    page: SearchResult,
+    seen: &mut HashSet<String>,
+    first_page: bool,
+) -> usize {
+    if first_page {
+        acc.total_results = page.total_results;
+        acc.total_count = page.total_count;
+        acc.top_assignees = page.top_assignees;
+        acc.top_cpcs = page.top_cpcs;
+    }
+
+    let before = acc.patents.len();
+    for patent in page.patents {
+        // "Unknown" IDs cannot be deduplicated reliably, so always keep them
+        if patent.id == "Unknown" || seen.insert(patent.id.clone()) {
+            acc.patents.push(patent);
+        }
+    }
+    acc.patents.len() - before
 }
 
 #[cfg(test)]
 fn parse_search_results(results: serde_json::Value, limit: Option<usize>) -> Result<SearchResult> {
     let mut sr: SearchResult = serde_json::from_value(results)?;
+    sr.total_count = crate::core::models::parse_result_count(&sr.total_results);
 
     if let Some(limit) = limit
         && sr.patents.len() > limit
@@ -299,6 +441,7 @@ mod tests {
         );
         assert_eq!(sr.patents[1].id, "DE102018215057B4");
         assert_eq!(sr.total_results, "1000");
+        assert_eq!(sr.total_count, Some(1000));
 
         let sr = parse_search_results(results, None).unwrap();
         assert_eq!(sr.patents.len(), 3);
@@ -306,46 +449,41 @@ mod tests {
     }
 
     #[test]
-    fn test_parse_single_patent() {
-        let result = json!({
-            "title": "System and method for interactive big data analysis",
-            "abstract": "A system and method for interactive big data analysis...",
-            "filing_date": "2013-08-06",
-            "assignee": "Google LLC",
-            "description_paragraphs": [
-                {"number": "0001", "id": "p1", "text": "CROSS-REFERENCE TO RELATED APPLICATIONS"}
-            ],
-            "claims": [
-                {"number": "1", "id": "c1", "text": "1. A non-transitory machine-readable storage medium..."}
-            ],
-            "images": [
-                {"url": "https://patentimages.storage.googleapis.com/.../US09152718-20151006-D00000.png", "figure_number": "D00000"}
-            ]
-        });
-
-        let patents = parse_single_patent_result(
-            result,
-            "US9152718B2",
-            "https://patents.google.com/patent/US9152718B2".to_string(),
+    fn test_merge_search_page_dedups() {
+        let page1 = parse_search_results(
+            json!({
+                "total_results": "About 25 results",
+                "top_assignees": [{"name": "Google LLC", "percentage": "40%"}],
+                "Patent": [
+                    {"id": "US1A", "title": "One", "url": "https://patents.google.com/patent/US1A"},
+                    {"id": "US2A", "title": "Two", "url": "https://patents.google.com/patent/US2A"}
+                ]
+            }),
+            None,
+        )
+        .unwrap();
+        let page2 = parse_search_results(
+            json!({
+                "total_results": "Unknown",
+                "Patent": [
+                    {"id": "US2A", "title": "Two", "url": "https://patents.google.com/patent/US2A"},
+                    {"id": "US3A", "title": "Three", "url": "https://patents.google.com/patent/US3A"}
+                ]
+            }),
+            None,
         )
         .unwrap();
-        assert_eq!(patents.len(), 1);
-        let p = &patents[0];
-        assert_eq!(p.id, "US9152718B2");
-        assert_eq!(p.title, "System and method for interactive big data analysis");
-        assert_eq!(
-            p.abstract_text.as_deref(),
-            Some("A system and method for interactive big data analysis...")
-        );
-        assert_eq!(p.filing_date.as_deref(), Some("2013-08-06"));
-        assert_eq!(p.assignee.as_deref(), Some("Google LLC"));
-
-        let paras = p.description_paragraphs.as_ref().unwrap();
-        assert_eq!(paras.len(), 1);
-        assert_eq!(paras[0].text, "CROSS-REFERENCE TO RELATED APPLICATIONS");
 
-        let claims = p.claims.as_ref().unwrap();
-        assert_eq!(claims.len(), 1);
-        assert!(claims[0].text.starts_with("1. A non-transitory machine-readable storage medium"));
+        let mut acc = SearchResult::default();
+        let mut seen = HashSet::new();
+        assert_eq!(merge_search_page(&mut acc, page1, &mut seen, true), 2);
+        assert_eq!(merge_search_page(&mut acc, page2, &mut seen, false), 1);
+
+        let ids: Vec<&str> = acc.patents.iter().map(|p| p.id.as_str()).collect();
+        assert_eq!(ids, vec!["US1A", "US2A", "US3A"]);
+        // Summary comes from the first page
+        assert_eq!(acc.total_results, "About 25 results");
+        assert_eq!(acc.total_count, Some(25));
+        assert_eq!(acc.top_assignees.as_ref().unwrap().len(), 1);
     }
 }
@@ -0,0 +1,95 @@
+//! Download the official publication PDF of a patent.
+
+use crate::core::batch;
+use crate::core::images;
+use crate::core::models::Patent;
+use crate::core::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Outcome of a PDF download
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct PdfDownload {
+    pub patent_id: String,
+    pub pdf_url: String,
+    /// Local file, always `<dir>/<patent_id>.pdf`
+    pub file: PathBuf,
+    /// The file was already present and was not downloaded again
+    pub skipped: bool,
+}
+
+/// Where the PDF of `patent_id` is stored under `dir`
+pub fn pdf_path(dir: &Path, patent_id: &str) -> PathBuf {
+    dir.join(format!("{}.pdf", batch::file_stem(patent_id)))
+}
+
+/// Download the publication PDF of `patent` into `dir`, unless it is already there.
+pub async fn download_pdf(patent: &Patent, dir: &Path, host: &str) -> Result<PdfDownload> {
+    let pdf_url = patent
+        .pdf_url
+        .clone()
+        .ok_or_else(|| Error::Other(format!("No PDF available for {}", patent.id)))?;
+    let file = pdf_path(dir, &patent.id);
+
+    let skipped = tokio::fs::metadata(&file).await.is_ok_and(|m| m.len() > 0);
+    if !skipped {
+        tokio::fs::create_dir_all(dir).await?;
+        let client =
+            reqwest::Client::builder().timeout(std::time::Duration::from_secs(120)).build()?;
+        images::download(&client, &pdf_url, host, &file, "application/pdf").await?;
+    }
+
+    Ok(PdfDownload { patent_id: patent.id.clone(), pdf_url, file, skipped })
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::core::test_support::{self, FakeResponse};
+
+    /// Fake host answering one request with a PDF body served as `content_type`
+    async fn fake_host(
+        content_type: &'static str,
+    ) -> (String, tokio::task::JoinHandle<Vec<String>>) {
+        test_support::fake_host(1, move |_, _| FakeResponse::ok(content_type, "%PDF-1.4")).await
+    }
+
+    fn patent() -> Patent {
+        Patent {
+            id: "US9152718B2".to_string(),
+            pdf_url: Some(
+                "https://patentimages.storage.googleapis.com/4d/2b/US9152718.pdf".to_string(),
+            ),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_download_pdf() {
+        let dir = tempfile::tempdir().unwrap();
+        let (host, server) = fake_host("application/pdf").await;
+
+        let download = download_pdf(&patent(), dir.path(), &host).await.unwrap();
+        assert_eq!(download.file, dir.path().join("US9152718B2.pdf"));
+        assert!(!download.skipped);
+        assert_eq!(std::fs::read_to_string(&download.file).unwrap(), "%PDF-1.4");
+        assert!(server.await.unwrap()[0].starts_with("GET /4d/2b/US9152718.pdf"));
+
+        // Already downloaded: no request is made
+        let download = download_pdf(&patent(), dir.path(), "http://127.0.0.1:1").await.unwrap();
+        assert!(download.skipped);
+    }
+
+    #[tokio::test]
+    async fn test_download_pdf_rejects_html_and_missing_link() {
+        let dir = tempfile::tempdir().unwrap();
+        let (host, _server) = fake_host("text/html").await;
+        let err = download_pdf(&patent(), dir.path(), &host).await.unwrap_err();
+        assert!(err.to_string().contains("text/html"));
+        assert!(!dir.path().join("US9152718B2.pdf").exists());
+
+        let no_pdf = Patent { id: "US1A".to_string(), ..Default::default() };
+        assert!(download_pdf(&no_pdf, dir.path(), &host).await.is_err());
+    }
+}
@@ -0,0 +1,153 @@
+//! Attach to an already running Chrome instead of launching one.
+//!
+//! Tabs are opened and closed through the DevTools HTTP endpoints (`/json/new`,
+//! `/json/close`) of the running browser, and driven with `CdpPage` as usual.
+//! The browser itself is never shut down: detaching only closes the tabs we opened.
+
+use crate::core::{Error, Result};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::sync::Mutex;
+use url::Url;
+
+pub struct RemoteBrowser {
+    client: reqwest::Client,
+    http_base: String,
+    /// IDs of the targets (tabs) opened by us
+    targets: Mutex<HashSet<String>>,
+}
+
+impl RemoteBrowser {
+    /// Connect to the browser behind a DevTools WebSocket URL
+    /// (e.g., `ws://127.0.0.1:9222/devtools/browser/<id>`) or its `http://host:port`.
+    pub async fn connect(debug_ws_url: &str) -> Result<Self> {
+        let http_base = http_base(debug_ws_url)?;
+        let client =
+            reqwest::Client::builder().timeout(std::time::Duration::from_secs(10)).build()?;
+
+        let version: Value = client
+            .get(format!("{}/json/version", http_base))
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| {
+                Error::Browser(format!("Failed to attach to browser at {}: {}", http_base, e))
+            })?
+            .json()
+            .await?;
+        if version["webSocketDebuggerUrl"].as_str().is_none() {
+            return Err(Error::Browser(format!("No DevTools endpoint found at {}", http_base)));
+        }
+
+        Ok(Self { client, http_base, targets: Mutex::new(HashSet::new()) })
+    }
+
+    /// Open a new tab and return its page WebSocket URL
+    pub async fn new_page(&self) -> Result<String> {
+        // Recent Chrome versions only accept PUT for /json/new
+        let target: Value = self
+            .client
+            .put(format!("{}/json/new?about:blank", self.http_base))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        let ws_url = target["webSocketDebuggerUrl"]
+            .as_str()
+            .ok_or_else(|| Error::Browser("New tab has no WebSocket URL".to_string()))?;
+        if let Some(id) = target["id"].as_str()
+            && let Ok(mut targets) = self.targets.lock()
+        {
+            targets.insert(id.to_string());
+        }
+        Ok(ws_url.to_string())
+    }
+
+    /// Close any tabs we opened that are still around, leaving the browser running
+    pub async fn detach(&self) {
+        let targets: Vec<String> = match self.targets.lock() {
+            Ok(mut targets) => targets.drain().collect(),
+            Err(_) => return,
+        };
+        for id in targets {
+            // Tabs closed by CdpPage::close are already gone; ignore those errors
+            let _ = self.client.get(format!("{}/json/close/{}", self.http_base, id)).send().await;
+        }
+    }
+}
+
+/// HTTP base (`http://host:port`) of the DevTools endpoint behind a WebSocket URL
+fn http_base(debug_ws_url: &str) -> Result<String> {
+    let url = Url::parse(debug_ws_url)?;
+    let scheme = match url.scheme() {
+        "ws" | "http" => "http",
+        "wss" | "https" => "https",
+        other => {
+            return Err(Error::Config(format!(
+                "Unsupported debug WebSocket URL scheme: {}",
+                other
+            )));
+        }
+    };
+    let host = url.host_str().ok_or_else(|| {
+        Error::Config(format!("No host in debug WebSocket URL: {}", debug_ws_url))
+    })?;
+    Ok(match url.port() {
+        Some(port) => format!("{}://{}:{}", scheme, host, port),
+        None => format!("{}://{}", scheme, host),
+    })
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::core::test_support::{self, FakeResponse};
+
+    /// Fake DevTools endpoint that answers `count` requests and records their request lines.
+    async fn fake_devtools(count: usize) -> (String, tokio::task::JoinHandle<Vec<String>>) {
+        let (base_url, handle) = test_support::fake_host(count, |line, addr| {
+            let body = if line.contains("/json/version") {
+                format!(r#"{{"webSocketDebuggerUrl":"ws://{}/devtools/browser/b1"}}"#, addr)
+            } else if line.contains("/json/new") {
+                format!(r#"{{"id":"T1","webSocketDebuggerUrl":"ws://{}/devtools/page/T1"}}"#, addr)
+            } else {
+                "Target is closing".to_string()
+            };
+            FakeResponse::ok("application/json", body)
+        })
+        .await;
+        (format!("{}/devtools/browser/b1", base_url.replace("http://", "ws://")), handle)
+    }
+
+    #[test]
+    fn test_http_base() {
+        assert_eq!(
+            http_base("ws://127.0.0.1:9222/devtools/browser/abc").unwrap(),
+            "http://127.0.0.1:9222"
+        );
+        assert_eq!(http_base("http://localhost:9222").unwrap(), "http://localhost:9222");
+        assert_eq!(
+            http_base("wss://example.com/devtools/browser/abc").unwrap(),
+            "https://example.com"
+        );
+        assert!(http_base("ftp://example.com").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_attach_and_detach() {
+        let (ws_url, server) = fake_devtools(3).await;
+
+        let browser = RemoteBrowser::connect(&ws_url).await.unwrap();
+        let page_url = browser.new_page().await.unwrap();
+        assert!(page_url.ends_with("/devtools/page/T1"));
+        browser.detach().await;
+
+        let requests = server.await.unwrap();
+        assert!(requests[0].starts_with("GET /json/version"));
+        assert!(requests[1].starts_with("PUT /json/new"));
+        assert!(requests[2].starts_with("GET /json/close/T1"));
+    }
+}
@@ -0,0 +1,138 @@
+use crate::core::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::time::Duration;
+
+/// Retry policy with exponential backoff and jitter.
+///
+/// Used around page navigation, where Google's bot detection and transient
+/// browser/network failures are most likely to show up.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct RetryPolicy {
+    /// Number of retries after the first attempt (0 disables retrying)
+    pub max_retries: u32,
+    /// Delay before the first retry, in milliseconds
+    pub initial_delay_ms: u64,
+    /// Upper bound for the delay between retries, in milliseconds
+    pub max_delay_ms: u64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self { max_retries: 3, initial_delay_ms: 2_000, max_delay_ms: 60_000 }
+    }
+}
+
+impl RetryPolicy {
+    /// Policy that never retries
+    pub fn none() -> Self {
+        Self { max_retries: 0, ..Default::default() }
+    }
+
+    /// Backoff before retry number `attempt` (0-based): doubles each time, capped at
+    /// `max_delay_ms`, with jitter picking a delay between half and the full value.
+    pub fn delay_for(&self, attempt: u32) -> Duration {
+        let base = self
+            .initial_delay_ms
+            .saturating_mul(2u64.saturating_pow(attempt))
+            .min(self.max_delay_ms);
+        let half = base / 2;
+        Duration::from_millis(half + fastrand::u64(0..=base - half))
+    }
+
+    /// Run `op`, retrying retryable errors with backoff.
+    pub async fn retry<T, F, Fut>(&self, verbose: bool, mut op: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let mut attempt = 0;
+        loop {
+            match op().await {
+                Err(e) if attempt < self.max_retries && is_retryable(&e) => {
+                    let delay = self.delay_for(attempt);
+                    if verbose {
+                        eprintln!(
+                            "{} (retry {}/{} in {:.1}s)",
+                            e,
+                            attempt + 1,
+                            self.max_retries,
+                            delay.as_secs_f64()
+                        );
+                    }
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+/// Errors worth retrying: bot detection and transient browser/network failures
+fn is_retryable(err: &Error) -> bool {
+    matches!(err, Error::RateLimited(_) | Error::Browser(_) | Error::Request(_))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[test]
+    fn test_delay_for_backoff() {
+        let policy = RetryPolicy { max_retries: 5, initial_delay_ms: 1_000, max_delay_ms: 5_000 };
+        for _ in 0..20 {
+            let d0 = policy.delay_for(0).as_millis();
+            assert!((500..=1_000).contains(&d0));
+            let d2 = policy.delay_for(2).as_millis();
+            assert!((2_000..=4_000).contains(&d2));
+            // Capped at max_delay_ms
+            let d10 = policy.delay_for(10).as_millis();
+            assert!((2_500..=5_000).contains(&d10));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retry_rate_limited() {
+        let policy = RetryPolicy { max_retries: 2, initial_delay_ms: 1, max_delay_ms: 1 };
+        let calls = AtomicU32::new(0);
+
+        // Succeeds on the last allowed attempt
+        let result = policy
+            .retry(false, || async {
+                if calls.fetch_add(1, Ordering::SeqCst) < 2 {
+                    Err(Error::RateLimited("Sorry...".to_string()))
+                } else {
+                    Ok(42)
+                }
+            })
+            .await;
+        assert_eq!(result.unwrap(), 42);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        // Gives up after max_retries
+        calls.store(0, Ordering::SeqCst);
+        let result: Result<()> = policy
+            .retry(false, || async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err(Error::RateLimited("Sorry...".to_string()))
+            })
+            .await;
+        assert!(matches!(result, Err(Error::RateLimited(_))));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        // Other errors are not retried
+        calls.store(0, Ordering::SeqCst);
+        let result: Result<()> = policy
+            .retry(false, || async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err(Error::Search("bad query".to_string()))
+            })
+            .await;
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+}
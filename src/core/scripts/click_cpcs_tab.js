@@ -24,7 +24,7 @@
         cpcsTab.click();
     }
 
-    // Click Expand button for CPCs after a small delay
+    // Click Expand button for CPCs
     const expandBtn = findElementByText('Expand');
     if (expandBtn) {
         expandBtn.click();
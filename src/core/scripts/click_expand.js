@@ -0,0 +1,27 @@
+// Script to click Expand in the summary box, showing all top assignees
+(() => {
+    function findElementByText(text, root = document) {
+        const walker = document.createTreeWalker(root, NodeFilter.SHOW_TEXT, null, false);
+        let node;
+        while (node = walker.nextNode()) {
+            if (node.textContent.trim() === text) {
+                return node.parentElement;
+            }
+        }
+        const allElements = root.querySelectorAll('*');
+        for (const el of allElements) {
+            if (el.shadowRoot) {
+                const found = findElementByText(text, el.shadowRoot);
+                if (found) return found;
+            }
+        }
+        return null;
+    }
+
+    const expandBtn = findElementByText('Expand');
+    if (expandBtn) {
+        expandBtn.click();
+    }
+
+    return { clicked: expandBtn !== null };
+})()
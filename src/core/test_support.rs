@@ -0,0 +1,184 @@
+//! Helpers shared by unit tests.
+
+use crate::core::models::{Patent, SearchOptions, SearchResult};
+use crate::core::patent_search::PatentSearch;
+use crate::core::{Error, Result};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+
+/// Patent lookup of a [`MockSearcher`]
+type Lookup = Box<dyn Fn(&str) -> Option<Patent> + Send + Sync>;
+
+/// Configurable [`PatentSearch`] serving patents from a lookup function.
+///
+/// Searches without a patent number return the patents given to
+/// [`MockSearcher::with_search_results`].
+pub(crate) struct MockSearcher {
+    lookup: Lookup,
+    errors: HashMap<String, fn() -> Error>,
+    search_results: fn() -> Vec<Patent>,
+    rate_limit_after: Option<usize>,
+    delay: Duration,
+    calls: AtomicUsize,
+    in_flight: AtomicUsize,
+    max_in_flight: AtomicUsize,
+}
+
+/// Patent titled "Mock Patent"
+pub(crate) fn mock_patent(id: &str) -> Patent {
+    Patent { id: id.to_string(), title: "Mock Patent".to_string(), ..Default::default() }
+}
+
+impl MockSearcher {
+    /// Mock serving the patents `lookup` returns; `None` means not found
+    pub fn new(lookup: impl Fn(&str) -> Option<Patent> + Send + Sync + 'static) -> Self {
+        Self {
+            lookup: Box::new(lookup),
+            errors: HashMap::new(),
+            search_results: Vec::new,
+            rate_limit_after: None,
+            delay: Duration::ZERO,
+            calls: AtomicUsize::new(0),
+            in_flight: AtomicUsize::new(0),
+            max_in_flight: AtomicUsize::new(0),
+        }
+    }
+
+    /// Mock serving every ID as a [`mock_patent`]
+    pub fn any() -> Self {
+        Self::new(|id| Some(mock_patent(id)))
+    }
+
+    /// Fail fetches of `id` with the error `error` builds
+    pub fn with_error(mut self, id: &str, error: fn() -> Error) -> Self {
+        self.errors.insert(id.to_string(), error);
+        self
+    }
+
+    /// Patents found by searches without a patent number
+    pub fn with_search_results(mut self, patents: fn() -> Vec<Patent>) -> Self {
+        self.search_results = patents;
+        self
+    }
+
+    /// Rate-limit every call after the first `calls`
+    pub fn rate_limit_after(mut self, calls: usize) -> Self {
+        self.rate_limit_after = Some(calls);
+        self
+    }
+
+    /// Wait `delay` before answering each call
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Number of calls made so far, rate-limited ones included
+    pub fn calls(&self) -> usize {
+        self.calls.load(Ordering::SeqCst)
+    }
+
+    /// Most calls that were in flight at the same time
+    pub fn max_in_flight(&self) -> usize {
+        self.max_in_flight.load(Ordering::SeqCst)
+    }
+
+    fn patent(&self, id: &str) -> Result<Option<Patent>> {
+        match self.errors.get(id) {
+            Some(error) => Err(error()),
+            None => Ok((self.lookup)(id)),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl PatentSearch for MockSearcher {
+    async fn search(&self, options: &SearchOptions) -> Result<SearchResult> {
+        let call = self.calls.fetch_add(1, Ordering::SeqCst);
+        let now = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        self.max_in_flight.fetch_max(now, Ordering::SeqCst);
+        tokio::time::sleep(self.delay).await;
+        self.in_flight.fetch_sub(1, Ordering::SeqCst);
+
+        if self.rate_limit_after.is_some_and(|n| call >= n) {
+            return Err(Error::RateLimited("Sorry...".to_string()));
+        }
+        let patents: Vec<Patent> = match &options.patent_number {
+            Some(id) => self.patent(id)?.into_iter().collect(),
+            None => (self.search_results)(),
+        };
+        Ok(SearchResult {
+            total_results: patents.len().to_string(),
+            total_count: Some(patents.len() as u64),
+            patents,
+            ..Default::default()
+        })
+    }
+
+    async fn fetch_raw_html(&self, options: &SearchOptions) -> Result<String> {
+        let id = options.patent_number.clone().unwrap_or_default();
+        match self.patent(&id)? {
+            Some(_) => {
+                Ok(format!("<html><title>{} - Raw Page - Google Patents</title></html>", id))
+            }
+            None => Err(Error::Search(format!("No patent page for {}", id))),
+        }
+    }
+}
+
+/// Response of a [`fake_host`]
+pub(crate) struct FakeResponse {
+    pub status: &'static str,
+    pub content_type: &'static str,
+    pub body: String,
+}
+
+impl FakeResponse {
+    /// `200 OK` with `body` of `content_type`
+    pub fn ok(content_type: &'static str, body: impl Into<String>) -> Self {
+        Self { status: "200 OK", content_type, body: body.into() }
+    }
+
+    /// HTML `body` with the given status line, e.g. `404 Not Found`
+    pub fn status(status: &'static str, body: impl Into<String>) -> Self {
+        Self { status, content_type: "text/html", body: body.into() }
+    }
+}
+
+/// Fake HTTP host answering `count` requests with the response `respond` builds from the
+/// request line and the host's own address. Returns the base URL and the request lines received.
+#[allow(clippy::unwrap_used)]
+pub(crate) async fn fake_host(
+    count: usize,
+    respond: impl Fn(&str, &str) -> FakeResponse + Send + 'static,
+) -> (String, JoinHandle<Vec<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap().to_string();
+    let base_url = format!("http://{}", addr);
+    let handle = tokio::spawn(async move {
+        let mut requests = Vec::new();
+        for _ in 0..count {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 4096];
+            let n = socket.read(&mut buf).await.unwrap();
+            let request = String::from_utf8_lossy(&buf[..n]);
+            let line = request.lines().next().unwrap_or_default().to_string();
+            let response = respond(&line, &addr);
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                response.status,
+                response.content_type,
+                response.body.len(),
+                response.body
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+            requests.push(line);
+        }
+        requests
+    });
+    (base_url, handle)
+}
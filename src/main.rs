@@ -1,4 +1,7 @@
 #[tokio::main]
-async fn main() -> anyhow::Result<()> {
-    google_patent_cli::cli::run().await
+async fn main() {
+    if let Err(e) = google_patent_cli::cli::run().await {
+        eprintln!("Error: {:?}", e);
+        std::process::exit(google_patent_cli::cli::exit_code(&e));
+    }
 }
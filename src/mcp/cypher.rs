@@ -1,6 +1,7 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::HashMap;
 
 /// Request parameters for executing Cypher query
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -21,3 +22,89 @@ pub struct ExecuteCypherResponse {
     #[schemars(description = "Number of rows returned")]
     pub row_count: usize,
 }
+
+/// Replace the `DEPENDS_ON` stubs of every claim list in `json` with copies of the claims
+/// they refer to, so one hop from a dependent claim reaches its parent's text and fields.
+///
+/// The engine builds its graph from the JSON tree, so an edge can only lead to a nested
+/// node, never to the parent's own `:claims` node.
+pub fn link_claim_dependencies(json: &mut Value) {
+    match json {
+        Value::Array(items) => items.iter_mut().for_each(link_claim_dependencies),
+        Value::Object(map) => {
+            if let Some(Value::Array(claims)) = map.get_mut("claims") {
+                link_claims(claims);
+            }
+            map.values_mut().for_each(link_claim_dependencies);
+        }
+        _ => {}
+    }
+}
+
+fn link_claims(claims: &mut [Value]) {
+    // Parents are copied without their own edges, which keeps the copies one level deep
+    let parents: HashMap<String, Value> = claims
+        .iter()
+        .filter_map(|claim| {
+            let id = claim.get("id")?.as_str()?.to_string();
+            let mut parent = claim.clone();
+            parent.as_object_mut()?.remove("DEPENDS_ON");
+            Some((id, parent))
+        })
+        .collect();
+    for claim in claims {
+        let Some(Value::Array(refs)) = claim.get_mut("DEPENDS_ON") else { continue };
+        for r in refs {
+            if let Some(parent) = r.get("id").and_then(Value::as_str).and_then(|id| parents.get(id))
+            {
+                *r = parent.clone();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_link_claim_dependencies() {
+        let mut patent = json!({
+            "id": "US1A",
+            "claims": [
+                {"number": "1", "id": "c1", "text": "A method.", "independent": true},
+                {
+                    "number": "2",
+                    "id": "c2",
+                    "text": "The method of claim 1.",
+                    "depends_on": [1],
+                    "DEPENDS_ON": [{"number": "1", "id": "c1"}]
+                },
+                {
+                    "number": "3",
+                    "id": "c3",
+                    "text": "The method of claim 2 or 9.",
+                    "depends_on": [2],
+                    "DEPENDS_ON": [{"number": "2", "id": "c2"}, {"number": "9", "id": "c9"}]
+                }
+            ]
+        });
+        // Claims nested deeper, e.g. in a batch of patents, are linked as well
+        let mut batch = json!({ "patents": [patent.clone()] });
+        link_claim_dependencies(&mut patent);
+        link_claim_dependencies(&mut batch);
+        assert_eq!(batch["patents"][0], patent);
+
+        let claims = &patent["claims"];
+        assert_eq!(claims[1]["DEPENDS_ON"][0]["text"], "A method.");
+        assert_eq!(claims[1]["DEPENDS_ON"][0]["independent"], true);
+        // The copy of claim 2 carries its own dependencies as numbers, not as edges
+        let parent = &claims[2]["DEPENDS_ON"][0];
+        assert_eq!(parent["text"], "The method of claim 1.");
+        assert_eq!(parent["depends_on"], json!([1]));
+        assert!(parent.get("DEPENDS_ON").is_none());
+        // Unknown claims keep their stub
+        assert_eq!(claims[2]["DEPENDS_ON"][1], json!({"number": "9", "id": "c9"}));
+    }
+}
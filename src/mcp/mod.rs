@@ -1,7 +1,20 @@
+use crate::core::batch::{self, BatchReport};
+use crate::core::cache::{self, CacheMode, PatentCache};
+use crate::core::claims::{self, ChartFormat, ClaimChart};
 use crate::core::config::Config;
-use crate::core::models::SearchOptions;
+use crate::core::crawl::{self, CrawlOptions};
+use crate::core::dates::{DateBound, parse_date_filter};
+use crate::core::extract;
+use crate::core::family;
+use crate::core::images::{self, DEFAULT_IMAGE_HOST, ImageFailure};
+use crate::core::lint::{self, LintReport};
+use crate::core::models::{
+    Dedup, PatentStatus, PatentType, SearchOptions, SortOrder, check_page_size,
+};
 use crate::core::models::{Patent, SearchResult};
-use crate::core::patent_search::{PatentSearch, PatentSearcher};
+use crate::core::patent_search::{PatentSearch, create_searcher};
+use crate::core::pdf::{self, PdfDownload};
+use futures::StreamExt;
 use rmcp::{
     ErrorData, RoleServer, ServerHandler, ServiceExt,
     handler::server::{tool::ToolRouter, wrapper::Parameters},
@@ -25,6 +38,25 @@ pub mod cypher;
 
 use cypher_rs::CypherEngine;
 
+/// A single value or a list of values
+#[derive(Deserialize, JsonSchema)]
+#[serde(untagged)]
+enum OneOrMany {
+    One(String),
+    Many(Vec<String>),
+}
+
+/// Accept `"JP"`, `"JP,US"` or `["JP", "US"]`, so clients written for the single-value
+/// parameter keep working.
+fn one_or_many<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Vec<String>>, D::Error> {
+    Ok(Option::<OneOrMany>::deserialize(deserializer)?.map(|value| match value {
+        OneOrMany::One(s) => s.split(',').map(|c| c.trim().to_string()).collect(),
+        OneOrMany::Many(list) => list,
+    }))
+}
+
 /// Request parameters for searching patents
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct SearchPatentsRequest {
@@ -34,33 +66,74 @@ pub struct SearchPatentsRequest {
     #[schemars(description = "Filter by assignee/applicant names")]
     pub assignee: Option<Vec<String>>,
 
-    #[schemars(description = "Filter by country code (e.g., 'JP', 'US', 'CN')")]
-    pub country: Option<String>,
+    #[schemars(description = "Filter by inventor names")]
+    pub inventor: Option<Vec<String>>,
+
+    #[schemars(description = "Filter by CPC classification codes (e.g., 'G06N20/00')")]
+    pub cpc: Option<Vec<String>>,
+
+    #[schemars(
+        description = "Filter by country codes (e.g., ['JP', 'US', 'EP', 'WO'] or 'JP')",
+        with = "Option<OneOrMany>"
+    )]
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub country: Option<Vec<String>>,
 
     // Priority date filters
-    #[schemars(description = "Filter by priority date after (YYYY-MM-DD)")]
+    #[schemars(
+        description = "Filter by priority date after (YYYY-MM-DD, YYYY-MM, YYYY or e.g. '-5y')"
+    )]
     pub priority_after: Option<String>,
 
-    #[schemars(description = "Filter by priority date before (YYYY-MM-DD)")]
+    #[schemars(
+        description = "Filter by priority date before (YYYY-MM-DD, YYYY-MM, YYYY or e.g. '-5y')"
+    )]
     pub priority_before: Option<String>,
 
     // Publication date filters
-    #[schemars(description = "Filter by publication date after (YYYY-MM-DD)")]
+    #[schemars(
+        description = "Filter by publication date after (YYYY-MM-DD, YYYY-MM, YYYY or e.g. '-5y')"
+    )]
     pub publication_after: Option<String>,
 
-    #[schemars(description = "Filter by publication date before (YYYY-MM-DD)")]
+    #[schemars(
+        description = "Filter by publication date before (YYYY-MM-DD, YYYY-MM, YYYY or e.g. '-5y')"
+    )]
     pub publication_before: Option<String>,
 
     // Filing date filters
-    #[schemars(description = "Filter by filing date after (YYYY-MM-DD)")]
+    #[schemars(
+        description = "Filter by filing date after (YYYY-MM-DD, YYYY-MM, YYYY or e.g. '-5y')"
+    )]
     pub filing_after: Option<String>,
 
-    #[schemars(description = "Filter by filing date before (YYYY-MM-DD)")]
+    #[schemars(
+        description = "Filter by filing date before (YYYY-MM-DD, YYYY-MM, YYYY or e.g. '-5y')"
+    )]
     pub filing_before: Option<String>,
 
+    #[schemars(description = "Filter by publication status ('grant' or 'application')")]
+    pub status: Option<PatentStatus>,
+
+    #[schemars(description = "Filter by patent type ('patent' or 'design')")]
+    #[serde(rename = "type")]
+    pub patent_type: Option<PatentType>,
+
+    #[schemars(description = "Only patents involved in litigation (default: false)")]
+    pub litigation: Option<bool>,
+
+    #[schemars(description = "Sort by date ('new' or 'old') instead of relevance")]
+    pub sort: Option<SortOrder>,
+
+    #[schemars(description = "Deduplicate by 'family' (default) or publication 'language'")]
+    pub dedup: Option<Dedup>,
+
     #[schemars(description = "Maximum number of results to return")]
     pub limit: Option<usize>,
 
+    #[schemars(description = "Results per page (10, 20, 50 or 100)")]
+    pub num: Option<usize>,
+
     #[schemars(description = "Language/locale for patent pages (e.g., 'ja', 'en', 'zh')")]
     pub language: Option<String>,
 }
@@ -69,6 +142,8 @@ impl Hash for SearchPatentsRequest {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.query.hash(state);
         self.assignee.hash(state);
+        self.inventor.hash(state);
+        self.cpc.hash(state);
         self.country.hash(state);
         self.priority_after.hash(state);
         self.priority_before.hash(state);
@@ -76,7 +151,13 @@ impl Hash for SearchPatentsRequest {
         self.publication_before.hash(state);
         self.filing_after.hash(state);
         self.filing_before.hash(state);
+        self.status.hash(state);
+        self.patent_type.hash(state);
+        self.litigation.hash(state);
+        self.sort.hash(state);
+        self.dedup.hash(state);
         self.limit.hash(state);
+        self.num.hash(state);
         self.language.hash(state);
     }
 }
@@ -85,6 +166,8 @@ impl PartialEq for SearchPatentsRequest {
     fn eq(&self, other: &Self) -> bool {
         self.query == other.query
             && self.assignee == other.assignee
+            && self.inventor == other.inventor
+            && self.cpc == other.cpc
             && self.country == other.country
             && self.priority_after == other.priority_after
             && self.priority_before == other.priority_before
@@ -92,7 +175,13 @@ impl PartialEq for SearchPatentsRequest {
             && self.publication_before == other.publication_before
             && self.filing_after == other.filing_after
             && self.filing_before == other.filing_before
+            && self.status == other.status
+            && self.patent_type == other.patent_type
+            && self.litigation == other.litigation
+            && self.sort == other.sort
+            && self.dedup == other.dedup
             && self.limit == other.limit
+            && self.num == other.num
             && self.language == other.language
     }
 }
@@ -100,13 +189,159 @@ impl PartialEq for SearchPatentsRequest {
 impl Eq for SearchPatentsRequest {}
 
 /// Request parameters for fetching a patent
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema, Hash, PartialEq, Eq)]
 pub struct FetchPatentRequest {
     #[schemars(description = "The patent ID (e.g., 'US9152718B2')")]
     pub patent_id: String,
 
     #[schemars(description = "Language/locale for patent pages (e.g., 'ja', 'en', 'zh')")]
     pub language: Option<String>,
+
+    #[schemars(description = "Fetch again even if the patent is cached (default: false)")]
+    pub refresh: Option<bool>,
+
+    #[schemars(description = "Serve only from the patent cache, without going online")]
+    pub offline: Option<bool>,
+
+    #[schemars(
+        description = "Also save the full page HTML next to the JSON output, for debugging extraction. The JSON is extracted from that page, bypassing the cache (default: false)"
+    )]
+    pub raw: Option<bool>,
+
+    #[schemars(
+        description = "Also download every figure, with a manifest mapping figure numbers to files (default: false)"
+    )]
+    pub download_images: Option<bool>,
+}
+
+/// Request parameters for fetching several patents
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct FetchPatentsRequest {
+    #[schemars(description = "The patent IDs to fetch (e.g., ['US9152718B2', 'JP2020000000A'])")]
+    pub patent_ids: Vec<String>,
+
+    #[schemars(description = "Language/locale for patent pages (e.g., 'ja', 'en', 'zh')")]
+    pub language: Option<String>,
+
+    #[schemars(
+        description = "Maximum number of patents fetched at the same time (default: 4, at most 8)"
+    )]
+    pub concurrency: Option<usize>,
+}
+
+/// Request parameters for expanding a patent family
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct FetchFamilyRequest {
+    #[schemars(description = "The patent ID the family is expanded from (e.g., 'US9152718B2')")]
+    pub patent_id: String,
+
+    #[schemars(description = "Language/locale for patent pages (e.g., 'ja', 'en', 'zh')")]
+    pub language: Option<String>,
+
+    #[schemars(
+        description = "Maximum number of family members fetched at the same time (default: 4, at most 8)"
+    )]
+    pub concurrency: Option<usize>,
+
+    #[schemars(description = "Fetch again even if the patents are cached (default: false)")]
+    pub refresh: Option<bool>,
+
+    #[schemars(description = "Serve only from the patent cache, without going online")]
+    pub offline: Option<bool>,
+}
+
+/// Request parameters for building a claim chart
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct ClaimChartRequest {
+    #[schemars(description = "The patent ID (e.g., 'US9152718B2')")]
+    pub patent_id: String,
+
+    #[schemars(description = "Language/locale for patent pages (e.g., 'ja', 'en', 'zh')")]
+    pub language: Option<String>,
+
+    #[schemars(description = "Table format ('markdown' or 'csv', default: 'markdown')")]
+    pub format: Option<ChartFormat>,
+}
+
+/// Request parameters for linting claims
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct LintClaimsRequest {
+    #[schemars(description = "Patent ID whose claims are checked (e.g., 'US9152718B2')")]
+    pub patent_id: Option<String>,
+
+    #[schemars(
+        description = "Check claims from a local JSON file instead: a patent, a list of claims or of claim texts"
+    )]
+    pub file: Option<String>,
+
+    #[schemars(description = "Language/locale for patent pages (e.g., 'ja', 'en', 'zh')")]
+    pub language: Option<String>,
+}
+
+/// Request parameters for downloading a publication PDF
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct DownloadPdfRequest {
+    #[schemars(description = "The patent ID (e.g., 'US9152718B2')")]
+    pub patent_id: String,
+
+    #[schemars(description = "Language/locale for patent pages (e.g., 'ja', 'en', 'zh')")]
+    pub language: Option<String>,
+
+    #[schemars(
+        description = "Directory the PDF is saved into as <patent_id>.pdf (default: a shared temp directory)"
+    )]
+    pub output_dir: Option<String>,
+}
+
+/// Request parameters for crawling the citation graph
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct CrawlCitationsRequest {
+    #[schemars(description = "Patent IDs the crawl starts from (e.g., ['US9152718B2'])")]
+    pub seeds: Vec<String>,
+
+    #[schemars(
+        description = "Hops followed through backward citations, i.e. what the seeds cite (default: 2)"
+    )]
+    pub backward_depth: Option<usize>,
+
+    #[schemars(
+        description = "Hops followed through forward citations, i.e. what cites the seeds (default: 1)"
+    )]
+    pub forward_depth: Option<usize>,
+
+    #[schemars(description = "Most patents fetched, seeds included (default: 200, at most 1000)")]
+    pub max_nodes: Option<usize>,
+
+    #[schemars(description = "Language/locale for patent pages (e.g., 'ja', 'en', 'zh')")]
+    pub language: Option<String>,
+
+    #[schemars(
+        description = "Maximum number of patents fetched at the same time (default: 4, at most 8)"
+    )]
+    pub concurrency: Option<usize>,
+}
+
+/// A result written by [`PatentHandler::write_dataset`]
+struct Dataset {
+    output_file: String,
+    schema: Value,
+    graph_schema: Option<String>,
+    name: String,
+}
+
+/// Patents returned by a batch fetch
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct BatchFetchResult {
+    #[serde(rename = "Patent")]
+    pub patents: Vec<Patent>,
+}
+
+/// Claim charts of a patent's independent claims
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ClaimChartResult {
+    pub patent_id: String,
+    #[serde(rename = "ClaimChart")]
+    pub charts: Vec<ClaimChart>,
 }
 
 /// Search result summary for returning to AI
@@ -127,9 +362,12 @@ pub struct SearchResultSummary {
     #[schemars(description = "Number of patents found")]
     pub count: usize,
 
-    #[schemars(description = "Total number of results from Google Patents")]
+    #[schemars(description = "Total number of results from Google Patents, as displayed")]
     pub total_results: String,
 
+    #[schemars(description = "Total number of results parsed as a number, if available")]
+    pub total_count: Option<u64>,
+
     #[schemars(description = "Top assignees with percentage breakdown")]
     pub top_assignees: Option<Vec<crate::core::models::SummaryItem>>,
 
@@ -151,6 +389,129 @@ pub struct FetchResultSummary {
 
     #[schemars(description = "Dataset name for Cypher queries")]
     pub dataset: Option<String>,
+
+    #[schemars(description = "Path to the raw page HTML (when requested with raw)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub raw_html_file: Option<String>,
+
+    #[schemars(description = "Figures downloaded (when requested with download_images)")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub images: Option<ImageSummary>,
+}
+
+/// Figures downloaded by fetch_patent
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ImageSummary {
+    #[schemars(description = "Path to the manifest mapping figure numbers to files")]
+    pub manifest_file: String,
+
+    #[schemars(description = "Number of figures available locally")]
+    pub count: usize,
+
+    #[schemars(description = "Figures that could not be downloaded")]
+    pub failed: Vec<ImageFailure>,
+}
+
+/// Batch fetch summary for returning to AI
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FetchPatentsSummary {
+    #[schemars(description = "Path to the output JSON file")]
+    pub output_file: String,
+
+    #[schemars(description = "JSON schema of the patent data")]
+    pub schema: Value,
+
+    #[schemars(description = "Graph schema for Cypher queries")]
+    pub graph_schema: Option<String>,
+
+    #[schemars(description = "Dataset name for Cypher queries")]
+    pub dataset: Option<String>,
+
+    #[schemars(description = "Number of patents requested, fetched, and the failures")]
+    pub report: BatchReport,
+}
+
+/// Family fetch summary for returning to AI
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FetchFamilySummary {
+    #[schemars(description = "Path to the output JSON file")]
+    pub output_file: String,
+
+    #[schemars(description = "JSON schema of the family record")]
+    pub schema: Value,
+
+    #[schemars(description = "Graph schema for Cypher queries")]
+    pub graph_schema: Option<String>,
+
+    #[schemars(description = "Dataset name for Cypher queries")]
+    pub dataset: Option<String>,
+
+    #[schemars(description = "Earliest priority date in the family (YYYY-MM-DD)")]
+    pub earliest_priority_date: Option<String>,
+
+    #[schemars(description = "Jurisdictions with at least one granted member")]
+    pub granted: Vec<String>,
+
+    #[schemars(description = "Jurisdictions with a pending member but no grant")]
+    pub pending: Vec<String>,
+
+    #[schemars(description = "Jurisdictions where every known member was abandoned")]
+    pub abandoned: Vec<String>,
+
+    #[schemars(description = "Number of family members (applications)")]
+    pub member_count: usize,
+}
+
+/// Claim chart summary for returning to AI
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ClaimChartSummary {
+    #[schemars(description = "Path to the output JSON file")]
+    pub output_file: String,
+
+    #[schemars(description = "JSON schema of the claim charts")]
+    pub schema: Value,
+
+    #[schemars(description = "Graph schema for Cypher queries")]
+    pub graph_schema: Option<String>,
+
+    #[schemars(description = "Dataset name for Cypher queries")]
+    pub dataset: Option<String>,
+
+    #[schemars(description = "Number of independent claims charted")]
+    pub claim_count: usize,
+
+    #[schemars(
+        description = "Chart table: one row per element (1[pre], 1[a], ...) with an empty evidence column"
+    )]
+    pub table: String,
+}
+
+/// Citation crawl summary for returning to AI
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CrawlCitationsSummary {
+    #[schemars(description = "Path to the output JSON file")]
+    pub output_file: String,
+
+    #[schemars(description = "JSON schema of the citation graph")]
+    pub schema: Value,
+
+    #[schemars(description = "Graph schema for Cypher queries")]
+    pub graph_schema: Option<String>,
+
+    #[schemars(description = "Dataset name for Cypher queries")]
+    pub dataset: Option<String>,
+
+    #[schemars(description = "Number of patents in the graph, fetched or only cited")]
+    pub node_count: usize,
+
+    #[schemars(description = "Number of patents fetched")]
+    pub fetched: usize,
+
+    #[schemars(description = "Number of CITES edges")]
+    pub edge_count: usize,
+
+    #[schemars(description = "Whether the crawl stopped because of max_nodes")]
+    pub budget_exhausted: bool,
 }
 
 /// Request parameters for patent analyzer skill
@@ -178,11 +539,41 @@ pub struct PatentAnalyzerRequest {
     pub raw: Option<bool>,
 }
 
+/// JSON-RPC error code for requests blocked by Google (bot detection / rate limiting)
+pub const RATE_LIMITED_ERROR_CODE: ErrorCode = ErrorCode(-32029);
+
+/// Suggested wait before retrying a rate-limited request
+const RATE_LIMIT_RETRY_AFTER_SECS: u64 = 60;
+
+/// Convert a search/fetch error into an MCP error, flagging rate limits as retryable.
+fn tool_error(context: &str, err: crate::core::Error) -> ErrorData {
+    let message = format!("{}: {}", context, err);
+    match err {
+        crate::core::Error::RateLimited(_) => ErrorData::new(
+            RATE_LIMITED_ERROR_CODE,
+            message,
+            Some(json!({
+                "kind": "rate_limited",
+                "retryable": true,
+                "retry_after_secs": RATE_LIMIT_RETRY_AFTER_SECS,
+            })),
+        ),
+        crate::core::Error::InvalidDate(_) => {
+            ErrorData::new(ErrorCode::INVALID_PARAMS, message, None)
+        }
+        _ => ErrorData::new(ErrorCode::INTERNAL_ERROR, message, None),
+    }
+}
+
 /// MCP handler for Google Patent CLI
 #[derive(Clone)]
 pub struct PatentHandler {
     tool_router: ToolRouter<PatentHandler>,
     searcher: Arc<dyn PatentSearch>,
+    // On-disk cache of fetched patents
+    cache: Option<Arc<PatentCache>>,
+    // Host figures are downloaded from
+    image_host: String,
     // Cypher store for auto-loading search results
     cypher_store: Arc<RwLock<HashMap<String, CypherEngine>>>,
 }
@@ -193,10 +584,24 @@ impl PatentHandler {
         Self {
             tool_router: Self::tool_router(),
             searcher,
+            cache: None,
+            image_host: DEFAULT_IMAGE_HOST.to_string(),
             cypher_store: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
+    /// Serve `fetch_patent` through an on-disk patent cache
+    pub fn with_cache(mut self, cache: PatentCache) -> Self {
+        self.cache = Some(Arc::new(cache));
+        self
+    }
+
+    /// Download figures from `host` instead of the default image host
+    pub fn with_image_host(mut self, host: impl Into<String>) -> Self {
+        self.image_host = host.into();
+        self
+    }
+
     /// Generate deterministic dataset name from search request
     fn dataset_name_from_request(request: &SearchPatentsRequest) -> String {
         let mut hasher = DefaultHasher::new();
@@ -205,6 +610,14 @@ impl PatentHandler {
         format!("search-{:x}", hash)
     }
 
+    /// Generate deterministic dataset name from a list of patent IDs
+    fn dataset_name_from_batch(patent_ids: &[String]) -> String {
+        let mut hasher = DefaultHasher::new();
+        patent_ids.hash(&mut hasher);
+        let hash = hasher.finish();
+        format!("batch-{:x}", hash)
+    }
+
     /// Generate deterministic dataset name from fetch request
     fn dataset_name_from_fetch(patent_id: &str) -> String {
         let mut hasher = DefaultHasher::new();
@@ -213,6 +626,31 @@ impl PatentHandler {
         format!("fetch-{:x}", hash)
     }
 
+    /// Generate deterministic dataset name from a charted patent
+    fn dataset_name_from_chart(patent_id: &str) -> String {
+        let mut hasher = DefaultHasher::new();
+        patent_id.hash(&mut hasher);
+        let hash = hasher.finish();
+        format!("chart-{:x}", hash)
+    }
+
+    /// Generate deterministic dataset name from a family seed
+    fn dataset_name_from_family(patent_id: &str) -> String {
+        let mut hasher = DefaultHasher::new();
+        patent_id.hash(&mut hasher);
+        let hash = hasher.finish();
+        format!("family-{:x}", hash)
+    }
+
+    /// Generate deterministic dataset name from a crawl request
+    fn dataset_name_from_crawl(seeds: &[String], options: &CrawlOptions) -> String {
+        let mut hasher = DefaultHasher::new();
+        seeds.hash(&mut hasher);
+        (options.backward_depth, options.forward_depth, options.max_nodes).hash(&mut hasher);
+        let hash = hasher.finish();
+        format!("crawl-{:x}", hash)
+    }
+
     /// Evict old datasets if exceeding max cache size
     async fn evict_old_datasets(&self) {
         const MAX_CACHE_SIZE: usize = 100;
@@ -229,14 +667,16 @@ impl PatentHandler {
     async fn load_to_cypher(
         &self,
         name: String,
-        json: &Value,
+        mut json: Value,
         root_label: Option<&str>,
     ) -> Option<String> {
+        cypher::link_claim_dependencies(&mut json);
+
         // Try to create engine from the provided JSON
         let engine_result = if let Some(label) = root_label {
-            CypherEngine::from_json_with_label(json, label)
+            CypherEngine::from_json_with_label(&json, label)
         } else {
-            CypherEngine::from_json_auto(json)
+            CypherEngine::from_json_auto(&json)
         };
 
         let engine = match engine_result {
@@ -262,30 +702,88 @@ impl PatentHandler {
         Some(graph_schema)
     }
 
+    /// Write `data` to a temp file `<prefix>-<uuid>.json`, load it into Cypher as `name`
+    /// and evict old datasets.
+    async fn write_dataset<T: Serialize + JsonSchema>(
+        &self,
+        prefix: &str,
+        name: String,
+        data: &T,
+    ) -> Result<Dataset, ErrorData> {
+        let schema = schema_for!(T);
+        let output_path =
+            std::env::temp_dir().join(format!("{}-{}.json", prefix, uuid::Uuid::new_v4()));
+        let json_str = serde_json::to_string_pretty(data).unwrap_or_default();
+
+        tokio::fs::write(&output_path, &json_str).await.map_err(|e| {
+            ErrorData::new(
+                ErrorCode::INTERNAL_ERROR,
+                format!("Failed to write to file {}: {}", output_path.display(), e),
+                None,
+            )
+        })?;
+
+        // Auto-load into Cypher for querying
+        let json_value: Value = serde_json::from_str(&json_str).unwrap_or_default();
+        let graph_schema = self.load_to_cypher(name.clone(), json_value, None).await;
+
+        // Evict old datasets if exceeding max cache size
+        self.evict_old_datasets().await;
+
+        Ok(Dataset {
+            output_file: output_path.to_string_lossy().into_owned(),
+            schema: serde_json::to_value(schema).unwrap_or_default(),
+            graph_schema,
+            name,
+        })
+    }
+
     /// Search Google Patents for patents matching a query
     #[tool(description = "Search Google Patents for patents matching a query")]
     pub async fn search_patents(
         &self,
         Parameters(request): Parameters<SearchPatentsRequest>,
     ) -> Result<String, ErrorData> {
+        let date = |value: &Option<String>, bound| {
+            value
+                .as_deref()
+                .map(|v| parse_date_filter(v, bound))
+                .transpose()
+                .map_err(|e| tool_error("Invalid search parameters", e))
+        };
         let options = SearchOptions {
             query: request.query.clone(),
             assignee: request.assignee.clone(),
+            inventor: request.inventor.clone(),
+            cpc: request.cpc.clone(),
             country: request.country.clone(),
             patent_number: None,
-            priority_after: request.priority_after.clone(),
-            priority_before: request.priority_before.clone(),
-            publication_after: request.publication_after.clone(),
-            publication_before: request.publication_before.clone(),
-            filing_after: request.filing_after.clone(),
-            filing_before: request.filing_before.clone(),
+            priority_after: date(&request.priority_after, DateBound::Start)?,
+            priority_before: date(&request.priority_before, DateBound::End)?,
+            publication_after: date(&request.publication_after, DateBound::Start)?,
+            publication_before: date(&request.publication_before, DateBound::End)?,
+            filing_after: date(&request.filing_after, DateBound::Start)?,
+            filing_before: date(&request.filing_before, DateBound::End)?,
+            status: request.status,
+            patent_type: request.patent_type,
+            litigation: request.litigation.unwrap_or(false),
+            sort: request.sort,
+            dedup: request.dedup,
             limit: request.limit,
+            num: request.num.map(check_page_size).transpose().map_err(|e| {
+                ErrorData::new(
+                    ErrorCode::INVALID_PARAMS,
+                    format!("Invalid search parameters: {}", e),
+                    None,
+                )
+            })?,
+            start_page: 0,
             language: request.language.clone(),
         };
+        options.validate_dates().map_err(|e| tool_error("Invalid search parameters", e))?;
 
-        let results = self.searcher.search(&options).await.map_err(|e| {
-            ErrorData::new(ErrorCode::INTERNAL_ERROR, format!("Search failed: {}", e), None)
-        })?;
+        let results =
+            self.searcher.search(&options).await.map_err(|e| tool_error("Search failed", e))?;
 
         let count = results.patents.len();
 
@@ -309,7 +807,7 @@ impl PatentHandler {
         // Auto-load into Cypher for querying
         let json_value: Value = serde_json::from_str(&json_str).unwrap_or_default();
         let dataset_name = Self::dataset_name_from_request(&request);
-        let graph_schema = self.load_to_cypher(dataset_name.clone(), &json_value, None).await;
+        let graph_schema = self.load_to_cypher(dataset_name.clone(), json_value, None).await;
 
         // Evict old datasets if exceeding max cache size
         self.evict_old_datasets().await;
@@ -322,6 +820,7 @@ impl PatentHandler {
             dataset: Some(dataset_name),
             count,
             total_results: results.total_results.clone(),
+            total_count: results.total_count,
             top_assignees: results.top_assignees.clone(),
             top_cpcs: results.top_cpcs.clone(),
         };
@@ -329,30 +828,52 @@ impl PatentHandler {
     }
 
     /// Fetch details of a specific patent by ID
-    #[tool(description = "Fetch details of a specific patent by ID")]
+    #[tool(
+        description = "Fetch details of a specific patent by ID. A dependent claim's DEPENDS_ON edges lead to copies of the claims it refers to, not to their :claims nodes, so walk a dependency chain one level per query: MATCH (c:claims)-[:DEPENDS_ON]->(p:DEPENDS_ON) RETURN c.number, p.number, p.text, p.depends_on"
+    )]
     pub async fn fetch_patent(
         &self,
         Parameters(request): Parameters<FetchPatentRequest>,
     ) -> Result<String, ErrorData> {
         let options = SearchOptions {
-            query: None,
-            assignee: None,
-            country: None,
             patent_number: Some(request.patent_id.clone()),
-            priority_after: None,
-            priority_before: None,
-            publication_after: None,
-            publication_before: None,
-            filing_after: None,
-            filing_before: None,
-            limit: None,
             language: request.language,
+            ..Default::default()
+        };
+        let raw = request.raw.unwrap_or(false);
+        let offline = request.offline.unwrap_or(false);
+        if raw && offline {
+            return Err(ErrorData::new(
+                ErrorCode::INVALID_PARAMS,
+                "raw cannot be combined with offline".to_string(),
+                None,
+            ));
+        }
+        let mode = CacheMode::from_flags(request.refresh.unwrap_or(false), offline);
+        let (patent, raw_html) = if raw {
+            // One page load yields both the saved HTML and the JSON extracted from it
+            let html = self
+                .searcher
+                .fetch_raw_html(&options)
+                .await
+                .map_err(|e| tool_error("Raw HTML fetch failed", e))?;
+            let url = options.to_url().map_err(|e| tool_error("Fetch failed", e))?;
+            let patent = extract::parse_patent_html(&html, &request.patent_id, &url);
+            if let Some(cache) = self.cache.as_deref() {
+                cache
+                    .put(&patent, options.language.as_deref())
+                    .map_err(|e| tool_error("Fetch failed", e))?;
+            }
+            (Some(patent), Some(html))
+        } else {
+            let patent =
+                cache::fetch_patent(self.searcher.as_ref(), self.cache.as_deref(), &options, mode)
+                    .await
+                    .map_err(|e| tool_error("Fetch failed", e))?;
+            (patent, None)
         };
-        let mut results = self.searcher.search(&options).await.map_err(|e| {
-            ErrorData::new(ErrorCode::INTERNAL_ERROR, format!("Fetch failed: {}", e), None)
-        })?;
 
-        let patent = results.patents.pop().ok_or_else(|| {
+        let patent = patent.ok_or_else(|| {
             ErrorData::new(
                 ErrorCode::INVALID_PARAMS,
                 format!("No patent found with ID: {}", request.patent_id),
@@ -365,8 +886,8 @@ impl PatentHandler {
 
         // Create temp file and write results
         let temp_dir = std::env::temp_dir();
-        let file_name = format!("patent-{}.json", uuid::Uuid::new_v4());
-        let output_path = temp_dir.join(&file_name);
+        let file_stem = format!("patent-{}", uuid::Uuid::new_v4());
+        let output_path = temp_dir.join(format!("{}.json", file_stem));
         let json_str = serde_json::to_string_pretty(&patent).unwrap_or_default();
 
         tokio::fs::write(&output_path, &json_str).await.map_err(|e| {
@@ -377,11 +898,42 @@ impl PatentHandler {
             )
         })?;
 
+        // Save the raw page next to the JSON output
+        let raw_html_file = if let Some(html) = raw_html {
+            let html_path = temp_dir.join(format!("{}.html", file_stem));
+            tokio::fs::write(&html_path, html).await.map_err(|e| {
+                ErrorData::new(
+                    ErrorCode::INTERNAL_ERROR,
+                    format!("Failed to write to file {}: {}", html_path.display(), e),
+                    None,
+                )
+            })?;
+            Some(html_path.to_string_lossy().into_owned())
+        } else {
+            None
+        };
+
+        // Figures go to a fixed directory, so figures already downloaded are reused
+        let images = if request.download_images.unwrap_or(false) {
+            let dir = temp_dir.join("patent-images");
+            let manifest = images::download_images(&patent, &dir, &self.image_host)
+                .await
+                .map_err(|e| tool_error("Image download failed", e))?;
+            let manifest_file = images::image_dir(&dir, &patent.id).join(images::MANIFEST_FILE);
+            Some(ImageSummary {
+                manifest_file: manifest_file.to_string_lossy().into_owned(),
+                count: manifest.figures.len(),
+                failed: manifest.failed,
+            })
+        } else {
+            None
+        };
+
         // Auto-load into Cypher for querying
         let json_value: Value = serde_json::from_str(&json_str).unwrap_or_default();
         let dataset_name = Self::dataset_name_from_fetch(&request.patent_id);
         let graph_schema =
-            self.load_to_cypher(dataset_name.clone(), &json_value, Some("Patent")).await;
+            self.load_to_cypher(dataset_name.clone(), json_value, Some("Patent")).await;
 
         // Evict old datasets if exceeding max cache size
         self.evict_old_datasets().await;
@@ -391,6 +943,353 @@ impl PatentHandler {
             schema: serde_json::to_value(schema).unwrap(),
             graph_schema,
             dataset: Some(dataset_name),
+            raw_html_file,
+            images,
+        };
+        Ok(serde_json::to_string_pretty(&summary).unwrap_or_default())
+    }
+
+    /// Fetch several patents by ID with bounded concurrency
+    #[tool(description = "Fetch details of several patents by ID")]
+    pub async fn fetch_patents(
+        &self,
+        Parameters(request): Parameters<FetchPatentsRequest>,
+    ) -> Result<String, ErrorData> {
+        let patent_ids: Vec<String> = batch::parse_id_list(&request.patent_ids.join("\n"));
+        if patent_ids.is_empty() {
+            return Err(ErrorData::new(
+                ErrorCode::INVALID_PARAMS,
+                "At least one patent ID must be provided".to_string(),
+                None,
+            ));
+        }
+
+        let mut report = BatchReport { requested: patent_ids.len(), ..Default::default() };
+        let items: Vec<batch::BatchItem> = batch::fetch_batch(
+            self.searcher.as_ref(),
+            self.cache.as_deref(),
+            patent_ids.clone(),
+            request.language,
+            CacheMode::Use,
+            request
+                .concurrency
+                .unwrap_or(batch::DEFAULT_CONCURRENCY)
+                .clamp(1, batch::MAX_CONCURRENCY),
+        )
+        .collect()
+        .await;
+
+        let mut patents = Vec::new();
+        for item in items {
+            report.record(&item);
+            if let Ok(patent) = item.result {
+                patents.push(patent);
+            }
+        }
+
+        let dataset = self
+            .write_dataset(
+                "patent-batch",
+                Self::dataset_name_from_batch(&patent_ids),
+                &BatchFetchResult { patents },
+            )
+            .await?;
+
+        let summary = FetchPatentsSummary {
+            output_file: dataset.output_file,
+            schema: dataset.schema,
+            graph_schema: dataset.graph_schema,
+            dataset: Some(dataset.name),
+            report,
+        };
+        Ok(serde_json::to_string_pretty(&summary).unwrap_or_default())
+    }
+
+    /// Fetch a patent and its family members, summarizing their status per jurisdiction
+    #[tool(
+        description = "Fetch a patent's family: which jurisdictions granted it, which are pending or abandoned, and the earliest priority date"
+    )]
+    pub async fn fetch_family(
+        &self,
+        Parameters(request): Parameters<FetchFamilyRequest>,
+    ) -> Result<String, ErrorData> {
+        let mode = CacheMode::from_flags(
+            request.refresh.unwrap_or(false),
+            request.offline.unwrap_or(false),
+        );
+        let family = family::fetch_family(
+            self.searcher.as_ref(),
+            self.cache.as_deref(),
+            &request.patent_id,
+            request.language,
+            mode,
+            request
+                .concurrency
+                .unwrap_or(batch::DEFAULT_CONCURRENCY)
+                .clamp(1, batch::MAX_CONCURRENCY),
+        )
+        .await
+        .map_err(|e| tool_error("Family fetch failed", e))?;
+
+        let family = family.ok_or_else(|| {
+            ErrorData::new(
+                ErrorCode::INVALID_PARAMS,
+                format!("No patent found with ID: {}", request.patent_id),
+                None,
+            )
+        })?;
+
+        let dataset = self
+            .write_dataset(
+                "patent-family",
+                Self::dataset_name_from_family(&request.patent_id),
+                &family,
+            )
+            .await?;
+        if family.rate_limited {
+            let message = format!(
+                "Google blocked a member fetch of {}; the partial family is in {}",
+                request.patent_id, dataset.output_file
+            );
+            return Err(tool_error(
+                "Family fetch stopped",
+                crate::core::Error::RateLimited(message),
+            ));
+        }
+
+        let summary = FetchFamilySummary {
+            output_file: dataset.output_file,
+            schema: dataset.schema,
+            graph_schema: dataset.graph_schema,
+            dataset: Some(dataset.name),
+            earliest_priority_date: family.earliest_priority_date.map(|d| d.to_string()),
+            granted: family.granted,
+            pending: family.pending,
+            abandoned: family.abandoned,
+            member_count: family.members.len(),
+        };
+        Ok(serde_json::to_string_pretty(&summary).unwrap_or_default())
+    }
+
+    /// Split a patent's independent claims into elements for a claim chart
+    #[tool(
+        description = "Build a claim chart: split each independent claim into preamble, transitional phrase and elements with stable IDs (1[pre], 1[a], 1[b]...)"
+    )]
+    pub async fn claim_chart(
+        &self,
+        Parameters(request): Parameters<ClaimChartRequest>,
+    ) -> Result<String, ErrorData> {
+        let options = SearchOptions {
+            patent_number: Some(request.patent_id.clone()),
+            language: request.language,
+            ..Default::default()
+        };
+        let patent = cache::fetch_patent(
+            self.searcher.as_ref(),
+            self.cache.as_deref(),
+            &options,
+            CacheMode::Use,
+        )
+        .await
+        .map_err(|e| tool_error("Fetch failed", e))?;
+
+        let patent = patent.ok_or_else(|| {
+            ErrorData::new(
+                ErrorCode::INVALID_PARAMS,
+                format!("No patent found with ID: {}", request.patent_id),
+                None,
+            )
+        })?;
+        let charts = claims::chart_claims(patent.claims.as_deref().unwrap_or_default());
+        if charts.is_empty() {
+            return Err(ErrorData::new(
+                ErrorCode::INVALID_PARAMS,
+                format!("No claims found for {}", request.patent_id),
+                None,
+            ));
+        }
+
+        let table = claims::render_chart(&charts, request.format.unwrap_or_default());
+        let claim_count = charts.len();
+        let result = ClaimChartResult { patent_id: patent.id, charts };
+        let dataset = self
+            .write_dataset(
+                "patent-chart",
+                Self::dataset_name_from_chart(&request.patent_id),
+                &result,
+            )
+            .await?;
+
+        let summary = ClaimChartSummary {
+            output_file: dataset.output_file,
+            schema: dataset.schema,
+            graph_schema: dataset.graph_schema,
+            dataset: Some(dataset.name),
+            claim_count,
+            table,
+        };
+        Ok(serde_json::to_string_pretty(&summary).unwrap_or_default())
+    }
+
+    /// Check claims for antecedent basis, reference and means-plus-function problems
+    #[tool(
+        description = "Lint claims: antecedent basis, references to missing or later claims, multiple dependent claims and 112(f) trigger words, each with a claim number and character offset"
+    )]
+    pub async fn lint_claims(
+        &self,
+        Parameters(request): Parameters<LintClaimsRequest>,
+    ) -> Result<String, ErrorData> {
+        let report: LintReport = match (request.patent_id, request.file) {
+            (None, Some(file)) => {
+                let text = tokio::fs::read_to_string(&file).await.map_err(|e| {
+                    ErrorData::new(
+                        ErrorCode::INVALID_PARAMS,
+                        format!("Failed to read file {}: {}", file, e),
+                        None,
+                    )
+                })?;
+                let claims = serde_json::from_str(&text)
+                    .map_err(crate::core::Error::from)
+                    .and_then(|value: Value| {
+                        let patent_id = value.get("id").and_then(Value::as_str).map(String::from);
+                        Ok((patent_id, lint::claims_from_json(&value)?))
+                    });
+                let (patent_id, claims) = claims.map_err(|e| {
+                    ErrorData::new(
+                        ErrorCode::INVALID_PARAMS,
+                        format!("Invalid claims file {}: {}", file, e),
+                        None,
+                    )
+                })?;
+                lint::lint_claims(patent_id, &claims)
+            }
+            (Some(patent_id), None) => {
+                let options = SearchOptions {
+                    patent_number: Some(patent_id.clone()),
+                    language: request.language,
+                    ..Default::default()
+                };
+                let patent = cache::fetch_patent(
+                    self.searcher.as_ref(),
+                    self.cache.as_deref(),
+                    &options,
+                    CacheMode::Use,
+                )
+                .await
+                .map_err(|e| tool_error("Fetch failed", e))?;
+                let patent = patent.ok_or_else(|| {
+                    ErrorData::new(
+                        ErrorCode::INVALID_PARAMS,
+                        format!("No patent found with ID: {}", patent_id),
+                        None,
+                    )
+                })?;
+                lint::lint_claims(Some(patent.id), patent.claims.as_deref().unwrap_or_default())
+            }
+            _ => {
+                return Err(ErrorData::new(
+                    ErrorCode::INVALID_PARAMS,
+                    "Exactly one of patent_id or file must be provided".to_string(),
+                    None,
+                ));
+            }
+        };
+        Ok(serde_json::to_string_pretty(&report).unwrap_or_default())
+    }
+
+    /// Download the official publication PDF of a patent
+    #[tool(description = "Download the official publication PDF of a patent as <patent_id>.pdf")]
+    pub async fn download_pdf(
+        &self,
+        Parameters(request): Parameters<DownloadPdfRequest>,
+    ) -> Result<String, ErrorData> {
+        let options = SearchOptions {
+            patent_number: Some(request.patent_id.clone()),
+            language: request.language,
+            ..Default::default()
+        };
+        let patent = cache::fetch_patent(
+            self.searcher.as_ref(),
+            self.cache.as_deref(),
+            &options,
+            CacheMode::Use,
+        )
+        .await
+        .map_err(|e| tool_error("Fetch failed", e))?;
+
+        let patent = patent.ok_or_else(|| {
+            ErrorData::new(
+                ErrorCode::INVALID_PARAMS,
+                format!("No patent found with ID: {}", request.patent_id),
+                None,
+            )
+        })?;
+
+        // PDFs go to a fixed directory by default, so PDFs already downloaded are reused
+        let dir = request
+            .output_dir
+            .map(std::path::PathBuf::from)
+            .unwrap_or_else(|| std::env::temp_dir().join("patent-pdfs"));
+        let download: PdfDownload = pdf::download_pdf(&patent, &dir, &self.image_host)
+            .await
+            .map_err(|e| tool_error("PDF download failed", e))?;
+        Ok(serde_json::to_string_pretty(&download).unwrap_or_default())
+    }
+
+    /// Crawl the citation graph around seed patents
+    #[tool(
+        description = "Walk backward and forward citations from seed patents up to a depth and node budget, loading it for Cypher queries. Query citations through the :edges nodes (from, to, examiner_cited, family), e.g. MATCH (e:edges) RETURN e.from, e.to, and patents through :Patent nodes. Each CITES edge leads to a stub node holding the cited publication_number, not to the cited :Patent node, so multi-hop CITES patterns match nothing"
+    )]
+    pub async fn crawl_citations(
+        &self,
+        Parameters(request): Parameters<CrawlCitationsRequest>,
+    ) -> Result<String, ErrorData> {
+        let seeds = batch::parse_id_list(&request.seeds.join("\n"));
+        if seeds.is_empty() {
+            return Err(ErrorData::new(
+                ErrorCode::INVALID_PARAMS,
+                "At least one seed patent ID must be provided".to_string(),
+                None,
+            ));
+        }
+
+        let defaults = CrawlOptions::default();
+        let options = CrawlOptions {
+            backward_depth: request.backward_depth.unwrap_or(defaults.backward_depth),
+            forward_depth: request.forward_depth.unwrap_or(defaults.forward_depth),
+            max_nodes: request.max_nodes.unwrap_or(defaults.max_nodes).min(crawl::MAX_NODES),
+            concurrency: request
+                .concurrency
+                .unwrap_or(defaults.concurrency)
+                .clamp(1, batch::MAX_CONCURRENCY),
+            language: request.language,
+            mode: CacheMode::Use,
+        };
+        let result =
+            crawl::crawl(self.searcher.as_ref(), self.cache.as_deref(), &seeds, &options, false)
+                .await
+                .map_err(|e| tool_error("Crawl failed", e))?;
+
+        let dataset = self
+            .write_dataset("patent-crawl", Self::dataset_name_from_crawl(&seeds, &options), &result)
+            .await?;
+        if result.rate_limited {
+            let message = format!(
+                "Google blocked the crawl after {} patents; the partial graph is in {}",
+                result.fetched, dataset.output_file
+            );
+            return Err(tool_error("Crawl stopped", crate::core::Error::RateLimited(message)));
+        }
+
+        let summary = CrawlCitationsSummary {
+            output_file: dataset.output_file,
+            schema: dataset.schema,
+            graph_schema: dataset.graph_schema,
+            dataset: Some(dataset.name),
+            node_count: result.nodes.len(),
+            fetched: result.fetched,
+            edge_count: result.edges.len(),
+            budget_exhausted: result.budget_exhausted,
         };
         Ok(serde_json::to_string_pretty(&summary).unwrap_or_default())
     }
@@ -466,18 +1365,23 @@ impl ServerHandler for PatentHandler {
 /// Run the MCP server over stdio
 pub async fn run() -> anyhow::Result<()> {
     let config = Config::load()?;
-    let (browser_path, chrome_args) = config.resolve();
-    let searcher = PatentSearcher::new(browser_path, true, false, false, chrome_args)
+    let cache = PatentCache::from_config(&config)
+        .map_err(|e| anyhow::anyhow!("Failed to open patent cache: {}", e))?;
+    let searcher = create_searcher(&config, config.backend, true, false, false)
         .await
         .map_err(|e| anyhow::anyhow!("Failed to create PatentSearcher: {}", e))?;
-    let handler = PatentHandler::new(Arc::new(searcher));
+    let handler =
+        PatentHandler::new(searcher.clone()).with_cache(cache).with_image_host(config.image_host());
 
-    let server = handler
-        .serve((stdin(), stdout()))
-        .await
-        .map_err(|e| anyhow::anyhow!("Failed to serve MCP server: {}", e))?;
-
-    server.waiting().await.map_err(|e| anyhow::anyhow!("MCP server error: {}", e))?;
+    let result = match handler.serve((stdin(), stdout())).await {
+        Ok(server) => {
+            server.waiting().await.map_err(|e| anyhow::anyhow!("MCP server error: {}", e))
+        }
+        Err(e) => Err(anyhow::anyhow!("Failed to serve MCP server: {}", e)),
+    };
+    // Leave an attached browser running, but close the tabs we opened
+    searcher.shutdown().await;
+    result?;
 
     Ok(())
 }
@@ -486,55 +1390,70 @@ pub async fn run() -> anyhow::Result<()> {
 #[allow(clippy::unwrap_used, clippy::expect_used)]
 mod tests {
     use super::*;
-    use crate::core::models::{Claim, Patent, SearchResult};
-
-    struct MockSearcher;
-
-    #[async_trait::async_trait]
-    impl PatentSearch for MockSearcher {
-        async fn search(&self, options: &SearchOptions) -> crate::core::Result<SearchResult> {
-            if let Some(pn) = &options.patent_number {
-                if pn == "FAIL" {
-                    return Err(crate::core::Error::Other("Mock failure".to_string()));
-                }
-                if pn == "NONE" {
-                    return Ok(SearchResult {
-                        total_results: "0".to_string(),
-                        patents: vec![],
-                        top_assignees: None,
-                        top_cpcs: None,
-                    });
-                }
-                return Ok(SearchResult {
-                    total_results: "1".to_string(),
-                    patents: vec![Patent {
-                        id: pn.clone(),
-                        title: "Mock Patent".to_string(),
-                        ..Default::default()
-                    }],
-                    top_assignees: None,
-                    top_cpcs: None,
-                });
-            }
-            Ok(SearchResult {
-                total_results: "1".to_string(),
-                patents: vec![Patent {
-                    id: "SEARCH1".to_string(),
-                    title: "Search Result".to_string(),
-                    ..Default::default()
-                }],
-                top_assignees: None,
-                top_cpcs: None,
+    use crate::core::models::{ApplicationInfo, Citation, Patent};
+    use crate::core::test_support::{MockSearcher, mock_patent};
+
+    /// Mock serving "Mock Patent" for any ID but NONE (not found), FAIL and BLOCKED
+    fn mock_searcher() -> Arc<MockSearcher> {
+        let searcher = MockSearcher::new(|id| {
+            (id != "NONE").then(|| Patent {
+                pdf_url: Some(format!("{}/pdf/{}.pdf", DEFAULT_IMAGE_HOST, id)),
+                family_applications: (id == "FAMILY_BLOCKED").then(|| {
+                    vec![ApplicationInfo {
+                        application_number: "WO2013000001".to_string(),
+                        country_code: None,
+                        publication_number: Some("BLOCKED".to_string()),
+                        priority_date: None,
+                        filing_date: None,
+                        title: None,
+                    }]
+                }),
+                claims: (id == "CLAIMS").then(|| {
+                    claims::parse_claims(
+                        [
+                            ("1", "c1", "1. A method comprising: receiving a query."),
+                            ("2", "c2", "2. The method of claim 1, wherein it is cached."),
+                        ]
+                        .map(|(n, id, text)| (n.to_string(), id.to_string(), text.to_string())),
+                    )
+                }),
+                ..mock_patent(id)
             })
-        }
+        })
+        .with_error("FAIL", || crate::core::Error::Other("Mock failure".to_string()))
+        .with_error("BLOCKED", || crate::core::Error::RateLimited("Sorry...".to_string()))
+        .with_search_results(|| {
+            vec![Patent {
+                id: "SEARCH1".to_string(),
+                title: "Search Result".to_string(),
+                ..Default::default()
+            }]
+        });
+        Arc::new(searcher)
+    }
+
+    #[test]
+    fn test_search_request_country() {
+        let parse = |country: Value| {
+            serde_json::from_value::<SearchPatentsRequest>(json!({ "country": country }))
+                .unwrap()
+                .country
+        };
+        assert_eq!(parse(json!("JP")), Some(vec!["JP".to_string()]));
+        assert_eq!(parse(json!("JP, US")), Some(vec!["JP".to_string(), "US".to_string()]));
+        assert_eq!(parse(json!(["JP", "US"])), Some(vec!["JP".to_string(), "US".to_string()]));
+        let request: SearchPatentsRequest = serde_json::from_value(json!({})).unwrap();
+        assert_eq!(request.country, None);
     }
 
     #[tokio::test]
     async fn test_search_patents() {
-        let handler = PatentHandler::new(Arc::new(MockSearcher));
+        let handler = PatentHandler::new(mock_searcher());
         let request = SearchPatentsRequest {
             query: Some("test".to_string()),
             assignee: None,
+            inventor: None,
+            cpc: None,
             country: None,
             priority_after: None,
             priority_before: None,
@@ -542,7 +1461,13 @@ mod tests {
             publication_before: None,
             filing_after: None,
             filing_before: None,
+            status: None,
+            patent_type: None,
+            litigation: None,
+            sort: None,
+            dedup: None,
             limit: None,
+            num: None,
             language: None,
         };
         let result = handler.search_patents(Parameters(request)).await;
@@ -566,12 +1491,40 @@ mod tests {
         let _ = tokio::fs::remove_file(&summary.output_file).await;
     }
 
+    #[tokio::test]
+    async fn test_search_patents_invalid_dates() {
+        let handler = PatentHandler::new(mock_searcher());
+        let request: SearchPatentsRequest = serde_json::from_value(json!({
+            "query": "test",
+            "filing_after": "2024/1/1"
+        }))
+        .unwrap();
+        let err = handler.search_patents(Parameters(request)).await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::INVALID_PARAMS);
+
+        let request: SearchPatentsRequest = serde_json::from_value(json!({
+            "query": "test",
+            "priority_after": "2021",
+            "priority_before": "2020"
+        }))
+        .unwrap();
+        let err = handler.search_patents(Parameters(request)).await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::INVALID_PARAMS);
+        assert!(err.message.contains("inverted"));
+
+        // Page sizes other than 10, 20, 50 or 100 are rejected, not rounded
+        let request: SearchPatentsRequest =
+            serde_json::from_value(json!({ "query": "test", "num": 7 })).unwrap();
+        let err = handler.search_patents(Parameters(request)).await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::INVALID_PARAMS);
+    }
+
     #[tokio::test]
     async fn test_fetch_patent() {
-        let handler = PatentHandler::new(Arc::new(MockSearcher));
+        let handler = PatentHandler::new(mock_searcher());
 
         // Success case
-        let request = FetchPatentRequest { patent_id: "US123".to_string(), language: None };
+        let request = FetchPatentRequest { patent_id: "US123".to_string(), ..Default::default() };
         let result = handler.fetch_patent(Parameters(request)).await;
         assert!(result.is_ok());
         let result_str = result.unwrap();
@@ -588,42 +1541,328 @@ mod tests {
         let _ = tokio::fs::remove_file(&summary.output_file).await;
 
         // Not found case
-        let request = FetchPatentRequest { patent_id: "NONE".to_string(), language: None };
+        let request = FetchPatentRequest { patent_id: "NONE".to_string(), ..Default::default() };
         let result = handler.fetch_patent(Parameters(request)).await;
         assert!(result.is_err());
         let err = result.unwrap_err();
         assert!(err.message.contains("No patent found"));
 
         // Error case
-        let request = FetchPatentRequest { patent_id: "FAIL".to_string(), language: None };
+        let request = FetchPatentRequest { patent_id: "FAIL".to_string(), ..Default::default() };
         let result = handler.fetch_patent(Parameters(request)).await;
         assert!(result.is_err());
         let err = result.unwrap_err();
         assert!(err.message.contains("Fetch failed"));
     }
 
+    #[tokio::test]
+    async fn test_fetch_patent_rate_limited() {
+        let handler = PatentHandler::new(mock_searcher());
+
+        // Other failures are internal errors without retry hints
+        let request = FetchPatentRequest { patent_id: "FAIL".to_string(), ..Default::default() };
+        let err = handler.fetch_patent(Parameters(request)).await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::INTERNAL_ERROR);
+        assert!(err.data.is_none());
+
+        // Rate limited: distinct code and retry hints
+        let request = FetchPatentRequest { patent_id: "BLOCKED".to_string(), ..Default::default() };
+        let err = handler.fetch_patent(Parameters(request)).await.unwrap_err();
+        assert_eq!(err.code, RATE_LIMITED_ERROR_CODE);
+        let data = err.data.unwrap();
+        assert_eq!(data["kind"], "rate_limited");
+        assert_eq!(data["retryable"], true);
+        assert_eq!(data["retry_after_secs"], RATE_LIMIT_RETRY_AFTER_SECS);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_patent_raw() {
+        let handler = PatentHandler::new(mock_searcher());
+
+        // Without raw, no HTML is saved
+        let request = FetchPatentRequest { patent_id: "US123".to_string(), ..Default::default() };
+        let result_str = handler.fetch_patent(Parameters(request)).await.unwrap();
+        let summary: FetchResultSummary = serde_json::from_str(&result_str).unwrap();
+        assert!(summary.raw_html_file.is_none());
+        let _ = tokio::fs::remove_file(&summary.output_file).await;
+
+        // Raw HTML is saved next to the JSON output
+        let request = FetchPatentRequest {
+            patent_id: "US123".to_string(),
+            raw: Some(true),
+            ..Default::default()
+        };
+        let result_str = handler.fetch_patent(Parameters(request)).await.unwrap();
+        let summary: FetchResultSummary = serde_json::from_str(&result_str).unwrap();
+        let raw_html_file = summary.raw_html_file.unwrap();
+        assert_eq!(raw_html_file, summary.output_file.replace(".json", ".html"));
+        let html = tokio::fs::read_to_string(&raw_html_file).await.unwrap();
+        assert!(html.contains("US123 - Raw Page"));
+        // The JSON is extracted from that same page, not from a separate fetch
+        let json = tokio::fs::read_to_string(&summary.output_file).await.unwrap();
+        assert_eq!(serde_json::from_str::<Value>(&json).unwrap()["title"], "Raw Page");
+        let _ = tokio::fs::remove_file(&summary.output_file).await;
+        let _ = tokio::fs::remove_file(&raw_html_file).await;
+    }
+
+    #[tokio::test]
+    async fn test_fetch_patent_images() {
+        let handler = PatentHandler::new(mock_searcher());
+
+        let request = FetchPatentRequest { patent_id: "US123".to_string(), ..Default::default() };
+        let result_str = handler.fetch_patent(Parameters(request)).await.unwrap();
+        let summary: FetchResultSummary = serde_json::from_str(&result_str).unwrap();
+        assert!(summary.images.is_none());
+        let _ = tokio::fs::remove_file(&summary.output_file).await;
+
+        // Figures are downloaded on request, with a manifest
+        let request = FetchPatentRequest {
+            patent_id: "US123".to_string(),
+            download_images: Some(true),
+            ..Default::default()
+        };
+        let result_str = handler.fetch_patent(Parameters(request)).await.unwrap();
+        let summary: FetchResultSummary = serde_json::from_str(&result_str).unwrap();
+        let images = summary.images.unwrap();
+        // The mock patent has no figures
+        assert_eq!(images.count, 0);
+        assert!(images.failed.is_empty());
+        assert!(images.manifest_file.ends_with("US123/manifest.json"));
+        let manifest = tokio::fs::read_to_string(&images.manifest_file).await.unwrap();
+        assert_eq!(serde_json::from_str::<Value>(&manifest).unwrap()["patent_id"], "US123");
+        let _ = tokio::fs::remove_file(&summary.output_file).await;
+    }
+
+    #[tokio::test]
+    async fn test_fetch_patents() {
+        let handler = PatentHandler::new(mock_searcher());
+        let request = FetchPatentsRequest {
+            patent_ids: vec!["US123".to_string(), "NONE".to_string(), "US456".to_string()],
+            ..Default::default()
+        };
+        let result = handler.fetch_patents(Parameters(request)).await.unwrap();
+        let summary: FetchPatentsSummary = serde_json::from_str(&result).unwrap();
+
+        assert_eq!(summary.report.requested, 3);
+        assert_eq!(summary.report.succeeded, 2);
+        assert_eq!(summary.report.failed[0].patent_id, "NONE");
+        assert!(!summary.report.rate_limited);
+        assert!(summary.dataset.unwrap().starts_with("batch-"));
+
+        let file_content = tokio::fs::read_to_string(&summary.output_file).await.unwrap();
+        assert!(file_content.contains("US123"));
+        assert!(file_content.contains("US456"));
+        let _ = tokio::fs::remove_file(&summary.output_file).await;
+
+        // Empty list is rejected
+        let request = FetchPatentsRequest::default();
+        assert!(handler.fetch_patents(Parameters(request)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_family() {
+        let handler = PatentHandler::new(mock_searcher());
+        let request = FetchFamilyRequest { patent_id: "US123B2".to_string(), ..Default::default() };
+        let result = handler.fetch_family(Parameters(request)).await.unwrap();
+        let summary: FetchFamilySummary = serde_json::from_str(&result).unwrap();
+
+        // The mock patent has no family applications: the seed is the only member
+        assert_eq!(summary.member_count, 1);
+        assert_eq!(summary.granted, ["US"]);
+        assert!(summary.dataset.unwrap().starts_with("family-"));
+        let file_content = tokio::fs::read_to_string(&summary.output_file).await.unwrap();
+        assert!(file_content.contains("US123B2"));
+        let _ = tokio::fs::remove_file(&summary.output_file).await;
+
+        let request = FetchFamilyRequest { patent_id: "NONE".to_string(), ..Default::default() };
+        let err = handler.fetch_family(Parameters(request)).await.unwrap_err();
+        assert!(err.message.contains("No patent found"));
+    }
+
+    #[tokio::test]
+    async fn test_download_pdf() {
+        let handler = PatentHandler::new(mock_searcher()).with_image_host("http://127.0.0.1:1");
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("US123.pdf"), "%PDF-1.4").unwrap();
+
+        // Already present: reported without going to the image host
+        let request = DownloadPdfRequest {
+            patent_id: "US123".to_string(),
+            output_dir: Some(dir.path().to_string_lossy().into_owned()),
+            ..Default::default()
+        };
+        let result = handler.download_pdf(Parameters(request)).await.unwrap();
+        let download: PdfDownload = serde_json::from_str(&result).unwrap();
+        assert!(download.skipped);
+        assert_eq!(download.file, dir.path().join("US123.pdf"));
+
+        let request = DownloadPdfRequest {
+            patent_id: "US456".to_string(),
+            output_dir: Some(dir.path().to_string_lossy().into_owned()),
+            ..Default::default()
+        };
+        let err = handler.download_pdf(Parameters(request)).await.unwrap_err();
+        assert!(err.message.contains("PDF download failed"));
+
+        let request = DownloadPdfRequest { patent_id: "NONE".to_string(), ..Default::default() };
+        let err = handler.download_pdf(Parameters(request)).await.unwrap_err();
+        assert!(err.message.contains("No patent found"));
+    }
+
+    #[tokio::test]
+    async fn test_claim_chart() {
+        let handler = PatentHandler::new(mock_searcher());
+        let request = ClaimChartRequest {
+            patent_id: "CLAIMS".to_string(),
+            format: Some(ChartFormat::Csv),
+            ..Default::default()
+        };
+        let result = handler.claim_chart(Parameters(request)).await.unwrap();
+        let summary: ClaimChartSummary = serde_json::from_str(&result).unwrap();
+        assert_eq!(summary.claim_count, 1);
+        assert!(
+            summary
+                .table
+                .starts_with("element,claim_language,evidence\n1[pre],A method comprising,")
+        );
+        assert!(summary.dataset.unwrap().starts_with("chart-"));
+        let file_content = tokio::fs::read_to_string(&summary.output_file).await.unwrap();
+        assert!(file_content.contains("\"ClaimChart\""));
+        let _ = tokio::fs::remove_file(&summary.output_file).await;
+
+        // Mock patents without claims have nothing to chart
+        let request = ClaimChartRequest { patent_id: "US123".to_string(), ..Default::default() };
+        let err = handler.claim_chart(Parameters(request)).await.unwrap_err();
+        assert!(err.message.contains("No claims found"));
+    }
+
+    #[tokio::test]
+    async fn test_lint_claims() {
+        let handler = PatentHandler::new(mock_searcher());
+        let request =
+            LintClaimsRequest { patent_id: Some("CLAIMS".to_string()), ..Default::default() };
+        let result = handler.lint_claims(Parameters(request)).await.unwrap();
+        let report: LintReport = serde_json::from_str(&result).unwrap();
+        assert_eq!(report.patent_id.as_deref(), Some("CLAIMS"));
+        assert_eq!(report.claims_checked, 2);
+        // The mock claims are clean
+        assert!(report.issues.is_empty());
+
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("claims.json");
+        std::fs::write(
+            &file,
+            r#"["1. A lens.", "2. The lens of claim 1, wherein the coating is hard."]"#,
+        )
+        .unwrap();
+        let request = LintClaimsRequest {
+            file: Some(file.to_string_lossy().into_owned()),
+            ..Default::default()
+        };
+        let result = handler.lint_claims(Parameters(request)).await.unwrap();
+        let report: LintReport = serde_json::from_str(&result).unwrap();
+        assert_eq!(report.issues.len(), 1);
+        assert_eq!(report.issues[0].claim, 2);
+        assert!(report.issues[0].message.contains("the coating"));
+
+        // Exactly one source is required
+        let err = handler.lint_claims(Parameters(LintClaimsRequest::default())).await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::INVALID_PARAMS);
+    }
+
+    #[tokio::test]
+    async fn test_crawl_citations() {
+        let handler = PatentHandler::new(mock_searcher());
+        let request = CrawlCitationsRequest {
+            seeds: vec!["US123".to_string(), "US456".to_string()],
+            ..Default::default()
+        };
+        let result = handler.crawl_citations(Parameters(request)).await.unwrap();
+        let summary: CrawlCitationsSummary = serde_json::from_str(&result).unwrap();
+
+        // Mock patents have no citations: the graph is just the seeds
+        assert_eq!(summary.node_count, 2);
+        assert_eq!(summary.fetched, 2);
+        assert_eq!(summary.edge_count, 0);
+        assert!(summary.dataset.unwrap().starts_with("crawl-"));
+        let _ = tokio::fs::remove_file(&summary.output_file).await;
+
+        let request = CrawlCitationsRequest::default();
+        let err = handler.crawl_citations(Parameters(request)).await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::INVALID_PARAMS);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_family_rate_limited() {
+        let handler = PatentHandler::new(mock_searcher());
+        let request =
+            FetchFamilyRequest { patent_id: "FAMILY_BLOCKED".to_string(), ..Default::default() };
+        let err = handler.fetch_family(Parameters(request)).await.unwrap_err();
+        assert_eq!(err.code, RATE_LIMITED_ERROR_CODE);
+        let output_file = err.message.rsplit(" is in ").next().unwrap();
+        assert!(tokio::fs::remove_file(output_file).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_crawl_citations_rate_limited() {
+        let handler = PatentHandler::new(mock_searcher());
+        let request = CrawlCitationsRequest {
+            seeds: vec!["US123".to_string(), "BLOCKED".to_string()],
+            ..Default::default()
+        };
+        let err = handler.crawl_citations(Parameters(request)).await.unwrap_err();
+        assert_eq!(err.code, RATE_LIMITED_ERROR_CODE);
+        // The partial graph is still saved
+        let output_file = err.message.rsplit(" is in ").next().unwrap();
+        assert!(tokio::fs::remove_file(output_file).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_patent_offline() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let cache = PatentCache::new(temp_dir.path(), std::time::Duration::from_secs(3600));
+        let handler = PatentHandler::new(mock_searcher()).with_cache(cache);
+
+        // Not cached yet
+        let request = FetchPatentRequest {
+            patent_id: "US123".to_string(),
+            offline: Some(true),
+            ..Default::default()
+        };
+        let result = handler.fetch_patent(Parameters(request.clone())).await;
+        assert!(result.unwrap_err().message.contains("not in the cache"));
+
+        // Fetch once to populate the cache, then offline succeeds
+        let online = FetchPatentRequest { patent_id: "US123".to_string(), ..Default::default() };
+        let summary: FetchResultSummary =
+            serde_json::from_str(&handler.fetch_patent(Parameters(online)).await.unwrap()).unwrap();
+        let _ = tokio::fs::remove_file(&summary.output_file).await;
+
+        let summary: FetchResultSummary =
+            serde_json::from_str(&handler.fetch_patent(Parameters(request)).await.unwrap())
+                .unwrap();
+        let _ = tokio::fs::remove_file(&summary.output_file).await;
+    }
+
     #[tokio::test]
     async fn test_claims_via_cypher_relationship() {
         let patent_with_claims = Patent {
             id: "US123".to_string(),
             title: "Test Patent".to_string(),
             abstract_text: Some("Test abstract".to_string()),
-            claims: Some(vec![
-                Claim {
-                    number: "1".to_string(),
-                    id: "c1".to_string(),
-                    text: "A method for doing X.".to_string(),
-                },
-                Claim {
-                    number: "2".to_string(),
-                    id: "c2".to_string(),
-                    text: "The method of claim 1, further comprising Y.".to_string(),
-                },
-            ]),
+            claims: Some(claims::parse_claims([
+                ("1".to_string(), "c1".to_string(), "A method for doing X.".to_string()),
+                (
+                    "2".to_string(),
+                    "c2".to_string(),
+                    "The method of claim 1, further comprising Y.".to_string(),
+                ),
+            ])),
             ..Default::default()
         };
 
-        let json_value = serde_json::to_value(&patent_with_claims).unwrap();
+        let mut json_value = serde_json::to_value(&patent_with_claims).unwrap();
+        cypher::link_claim_dependencies(&mut json_value);
         let engine = CypherEngine::from_json_with_label(&json_value, "Patent").unwrap();
 
         // Verify claims are accessible via label directly
@@ -643,6 +1882,28 @@ mod tests {
         assert_eq!(arr.len(), 2);
         assert_eq!(arr[0]["c.text"], "A method for doing X.");
 
+        // Dependent claims point at the claims they refer to
+        let result = engine
+            .execute("MATCH (c:claims)-[:DEPENDS_ON]->(d:DEPENDS_ON) RETURN c.id, d.id")
+            .unwrap();
+        let json = result.as_json_array();
+        let arr = json.as_array().unwrap();
+        assert_eq!(arr.len(), 1);
+        assert_eq!(arr[0]["c.id"], "c2");
+        assert_eq!(arr[0]["d.id"], "c1");
+
+        // One hop reaches a copy of the parent claim
+        let result = engine
+            .execute("MATCH (c:claims)-[:DEPENDS_ON]->(p:DEPENDS_ON) RETURN c.number, p.text")
+            .unwrap();
+        let json = result.as_json_array();
+        assert_eq!(json[0]["p.text"], "A method for doing X.");
+
+        let result = engine.execute("MATCH (c:claims) RETURN c.id, c.independent").unwrap();
+        let json = result.as_json_array();
+        assert_eq!(json[0]["c.independent"], true);
+        assert_eq!(json[1]["c.independent"], false);
+
         // Verify p.claims is null
         let result = engine.execute("MATCH (p:Patent) RETURN p.claims").unwrap();
         let json = result.as_json_array();
@@ -650,6 +1911,67 @@ mod tests {
         assert_eq!(arr[0]["p.claims"], "null");
     }
 
+    #[test]
+    fn test_citations_via_cypher_relationship() {
+        let citation = |number: &str, examiner_cited: bool| Citation {
+            publication_number: number.to_string(),
+            priority_date: None,
+            publication_date: None,
+            assignee: None,
+            title: None,
+            examiner_cited,
+            family: false,
+        };
+        let patent = Patent {
+            id: "US9152718B2".to_string(),
+            title: "Test Patent".to_string(),
+            url: "https://patents.google.com/patent/US9152718B2".to_string(),
+            citations: Some(vec![citation("US6038561A", true), citation("US20120089595A1", false)]),
+            cited_by: Some(vec![citation("US10430111B2", false)]),
+            ..Default::default()
+        };
+
+        let json_value = serde_json::to_value(&patent).unwrap();
+        let engine = CypherEngine::from_json_with_label(&json_value, "Patent").unwrap();
+
+        let result = engine
+            .execute("MATCH (p:Patent)-[:citations]->(c:citations) RETURN c.publication_number")
+            .unwrap();
+        let json = result.as_json_array();
+        assert_eq!(json.as_array().unwrap().len(), 2);
+        assert_eq!(json[0]["c.publication_number"], "US6038561A");
+
+        let result = engine.execute("MATCH (c:cited_by) RETURN c.publication_number").unwrap();
+        let json = result.as_json_array();
+        assert_eq!(json[0]["c.publication_number"], "US10430111B2");
+    }
+
+    #[test]
+    fn test_crawl_edges_via_cypher() {
+        let result = crawl::CrawlResult {
+            seeds: vec!["S".to_string()],
+            fetched: 2,
+            budget_exhausted: false,
+            rate_limited: false,
+            edges: vec![crawl::CitationEdge {
+                from: "S".to_string(),
+                to: "B1".to_string(),
+                examiner_cited: true,
+                family: false,
+            }],
+            nodes: vec![],
+        };
+        let engine = CypherEngine::from_json_auto(&serde_json::to_value(&result).unwrap()).unwrap();
+
+        let result =
+            engine.execute("MATCH (e:edges) RETURN e.from, e.to, e.examiner_cited").unwrap();
+        let json = result.as_json_array();
+        assert_eq!(json.as_array().unwrap().len(), 1);
+        assert_eq!(json[0]["e.from"], "S");
+        assert_eq!(json[0]["e.to"], "B1");
+        assert_eq!(json[0]["e.examiner_cited"], true);
+    }
+
     #[test]
     fn test_parse_text_property() {
         let engine = CypherEngine::from_json_with_label(